@@ -3,6 +3,7 @@ extern crate reformation;
 use reformation::Reformation;
 
 #[derive(Debug, Reformation)]
+#[allow(dead_code)]
 #[reformation(r"Vec\{{{x}, {y}, {z}\}}")]
 struct Vec{
     x: f32,
@@ -11,6 +12,7 @@ struct Vec{
 }
 
 #[derive(Debug, Reformation)]
+#[allow(dead_code)]
 #[reformation(r"Rect\{{{a}, {b}\}}")]
 struct Rect{
     a: Vec,
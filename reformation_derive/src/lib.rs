@@ -7,38 +7,47 @@ extern crate syn;
 
 extern crate proc_macro;
 
-use std::collections::HashSet;
-use std::ops::Deref;
-
 use proc_macro2::TokenStream;
 use syn::spanned::Spanned;
 use syn::{Attribute, AttrStyle};
-use syn::{DeriveInput, Data, Field, Fields};
+use syn::{DeriveInput, Data, DataEnum, Field, Fields, Variant};
 use syn::{GenericParam, Generics};
-use syn::{Type, Ident};
-use syn::{Expr, Lit};
+use syn::{Type, Ident, LitStr, LitInt, LitChar};
+use syn::parse::{Parse, ParseStream};
 
 
 #[proc_macro_derive(Reformation, attributes(reformation))]
 pub fn reformation_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream{
-    let mut ds = parse_macro_input!(item as DeriveInput);
+    let ds = parse_macro_input!(item as DeriveInput);
 
-    add_trait_bounds(&mut ds.generics);
+    if let Data::Enum(ref de) = ds.data{
+        let expanded = match impl_enum_body(&ds, de){
+            Ok(ok) => ok,
+            Err(errors) => errors
+        };
+        return proc_macro::TokenStream::from(expanded);
+    }
 
-    // find #[re_parse] a
-    let regex_tts = ds.attrs.iter()
-        .filter_map(get_re_parse_attribute)
-        .next();
+    // find #[reformation(..)] attributes
+    let regex_tts = merged_re_parse_attributes(&ds.attrs);
     let regex_tts = if let Some(regex_tts) = regex_tts{
-        proc_macro::TokenStream::from(regex_tts.clone())
+        proc_macro::TokenStream::from(regex_tts)
     }else{
         return proc_macro::TokenStream::from(quote!{
-            compile_error!{"Attribute #[re_parse(r\"..\")] containing format string not found."}
+            compile_error!{"Attribute #[reformation(r\"..\")] containing format string not found."}
         });
     };
-    let re = parse_macro_input!(regex_tts as Expr);
+    let mut attr = parse_macro_input!(regex_tts as StructAttribute);
 
-    let expanded = match impl_from_str_body(re, &ds){
+    let expanded = if attr.raw{
+        impl_raw_body(&attr, &ds)
+    }else{
+        match attr.replace_discarded(){
+            Ok(discarded) => impl_from_str_body(&attr, &ds, &discarded),
+            Err(e) => Err(e.to_compile_error()),
+        }
+    };
+    let expanded = match expanded{
         Ok(ok) => ok,
         Err(errors) => errors
     };
@@ -46,16 +55,419 @@ pub fn reformation_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStr
     proc_macro::TokenStream::from(expanded)
 }
 
+#[proc_macro_derive(ReformationRef, attributes(reformation))]
+pub fn reformation_ref_derive(item: proc_macro::TokenStream) -> proc_macro::TokenStream{
+    let ds = parse_macro_input!(item as DeriveInput);
+
+    let regex_tts = merged_re_parse_attributes(&ds.attrs);
+    let regex_tts = if let Some(regex_tts) = regex_tts{
+        proc_macro::TokenStream::from(regex_tts)
+    }else{
+        return proc_macro::TokenStream::from(quote!{
+            compile_error!{"Attribute #[reformation(r\"..\")] containing format string not found."}
+        });
+    };
+    let attr = parse_macro_input!(regex_tts as StructAttribute);
+
+    let expanded = match impl_ref_body(&attr, &ds){
+        Ok(ok) => ok,
+        Err(errors) => errors
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+
+/// Arguments of struct level attribute `#[reformation(r"format", args..)]`
+struct StructAttribute{
+    format: LitStr,
+    /// `r"format", r"alternative"..`: additional formats, tried if previous do not match
+    alternatives: Vec<LitStr>,
+    /// `error = Type`: error type of generated `FromStr` implementation
+    error: Option<Type>,
+    /// `prefix = "..."`: literal string preceding format
+    prefix: Option<LitStr>,
+    /// `suffix = "..."`: literal string following format
+    suffix: Option<LitStr>,
+    /// `try_parse_ref`: generate non allocating on failure `try_parse_ref` method
+    try_parse_ref: bool,
+    /// `is`: generate method `is`, comparing parsed input with expected value
+    is: bool,
+    /// `nom`: generate method `parse_nom`, usable as `nom` parser
+    nom: bool,
+    /// `canonical`: generate `canonical` method, rendering value with first format
+    canonical: bool,
+    /// `raw`: format is regular expression, fields are bound to named groups
+    raw: bool,
+    /// `greedy = false`: make repetitions in regular expressions of fields lazy
+    greedy: bool,
+    /// `unicode = false`: disable Unicode mode of regular expression
+    unicode: bool,
+    /// `verbose_pattern`: generate regular expression in extended mode, commenting placeholders
+    verbose_pattern: bool,
+    /// `reparse`: generate method `reparse`, parsing into existing value
+    reparse: bool,
+    /// `string_default = "greedy" | "lazy" | "non_ws"`: regular expression of `String`,
+    /// `Option<String>` and borrowed `&str` fields
+    string_default: Option<LitStr>,
+    /// `word_start`: match starts at word boundary `\b`
+    word_start: bool,
+    /// `word_end`: match ends at word boundary `\b`
+    word_end: bool,
+    /// `try_from`: implement `TryFrom<&str>`, `TryFrom<String>` and `TryFrom<&String>`
+    try_from: bool,
+}
+
+impl StructAttribute{
+    /// Format string, surrounded with escaped prefix and suffix, and word boundaries.
+    /// Each of multiple alternative formats is placed into its own capture group.
+    fn format_string(&self)->String{
+        let format = if self.alternatives.is_empty(){
+            self.format.value()
+        }else{
+            let alternatives: Vec<_> = self.formats()
+                .map(|f| format!("({})", f.value()))
+                .collect();
+            format!("(?:{})", alternatives.join("|"))
+        };
+        let start = if self.word_start{ r"\b" }else{ "" };
+        let end = if self.word_end{ r"\b" }else{ "" };
+        format!("{}{}{}", start, self.surround(&format), end)
+    }
+
+    /// First format string, surrounded with escaped prefix and suffix.
+    fn first_format_string(&self)->String{
+        self.surround(&self.format.value())
+    }
+
+    fn surround(&self, format: &str)->String{
+        let literal = |s: &Option<LitStr>|{
+            s.as_ref().map(|s| escape_format(&escape_regex(&s.value()))).unwrap_or_default()
+        };
+        format!("{}{}{}", literal(&self.prefix), format, literal(&self.suffix))
+    }
+
+    /// Replace discarded placeholders `{_}` and `{_:Type}` in all formats with
+    /// `{_discardedN}`, returning names and types of discarded values.
+    fn replace_discarded(&mut self)->syn::Result<Vec<(Ident, Type)>>{
+        let mut discarded = vec![];
+        self.format = replace_discarded(&self.format, &mut discarded)?;
+        for alternative in &mut self.alternatives{
+            *alternative = replace_discarded(alternative, &mut discarded)?;
+        }
+        Ok(discarded)
+    }
+
+    fn formats(&self)->impl Iterator<Item=&LitStr>{
+        Some(&self.format).into_iter().chain(&self.alternatives)
+    }
+}
+
+impl Parse for StructAttribute{
+    fn parse(input: ParseStream)->syn::Result<Self>{
+        let content;
+        parenthesized!(content in input);
+        let format = content.parse()?;
+        let mut alternatives = vec![];
+        let mut error = None;
+        let mut prefix = None;
+        let mut suffix = None;
+        let mut try_parse_ref = false;
+        let mut is = false;
+        let mut nom = false;
+        let mut canonical = false;
+        let mut raw = false;
+        let mut greedy = true;
+        let mut unicode = true;
+        let mut verbose_pattern = false;
+        let mut reparse = false;
+        let mut string_default = None;
+        let mut word_start = false;
+        let mut word_end = false;
+        let mut try_from = false;
+        while !content.is_empty(){
+            content.parse::<Token![,]>()?;
+            if content.is_empty(){
+                break;
+            }
+            if content.peek(LitStr){
+                alternatives.push(content.parse()?);
+                continue;
+            }
+            let name: Ident = content.parse()?;
+            match name.to_string().as_str(){
+                "error" => {
+                    content.parse::<Token![=]>()?;
+                    error = Some(content.parse()?);
+                },
+                "prefix" => {
+                    content.parse::<Token![=]>()?;
+                    prefix = Some(content.parse()?);
+                },
+                "suffix" => {
+                    content.parse::<Token![=]>()?;
+                    suffix = Some(content.parse()?);
+                },
+                "try_parse_ref" => {
+                    try_parse_ref = true;
+                },
+                "is" => {
+                    is = true;
+                },
+                "nom" => {
+                    nom = true;
+                },
+                "canonical" => {
+                    canonical = true;
+                },
+                "raw" => {
+                    raw = true;
+                },
+                "greedy" => {
+                    content.parse::<Token![=]>()?;
+                    greedy = content.parse::<syn::LitBool>()?.value;
+                },
+                "unicode" => {
+                    content.parse::<Token![=]>()?;
+                    unicode = content.parse::<syn::LitBool>()?.value;
+                },
+                "verbose_pattern" => {
+                    verbose_pattern = true;
+                },
+                "reparse" => {
+                    reparse = true;
+                },
+                "word_start" => {
+                    word_start = true;
+                },
+                "word_end" => {
+                    word_end = true;
+                },
+                "word_boundaries" => {
+                    word_start = true;
+                    word_end = true;
+                },
+                "try_from" => {
+                    try_from = true;
+                },
+                "string_default" => {
+                    content.parse::<Token![=]>()?;
+                    let mode: LitStr = content.parse()?;
+                    if string_regex(&mode.value()).is_none(){
+                        return Err(syn::Error::new(mode.span(), "String default must be \"greedy\", \"lazy\" or \"non_ws\"."));
+                    }
+                    string_default = Some(mode);
+                },
+                other => {
+                    let msg = format!("Unknown reformation argument `{}`.", other);
+                    return Err(syn::Error::new(name.span(), msg));
+                }
+            }
+        }
+        Ok(Self{format, alternatives, error, prefix, suffix, try_parse_ref, is, nom, canonical, raw, greedy, unicode, verbose_pattern, reparse, string_default, word_start, word_end, try_from})
+    }
+}
+
+
+fn replace_discarded(format: &LitStr, discarded: &mut Vec<(Ident, Type)>)->syn::Result<LitStr>{
+    let value = segments(&format.value()).iter()
+        .map(|segment| match segment{
+            Segment::Field(arg) if arg == "_" || arg.starts_with("_:") => {
+                // untyped placeholder matches any string
+                let ty = match arg.get(2..){
+                    Some(ty) => syn::parse_str(ty.trim()).map_err(|_|{
+                        let msg = format!("Cannot parse type `{}` of discarded placeholder.", ty.trim());
+                        syn::Error::new(format.span(), msg)
+                    })?,
+                    None => parse_quote!(String),
+                };
+                let ident = Ident::new(&format!("_discarded{}", discarded.len()), format.span());
+                let placeholder = format!("{{{}}}", ident);
+                discarded.push((ident, ty));
+                Ok(placeholder)
+            },
+            Segment::Literal(literal) => Ok(escape_format(literal)),
+            Segment::Field(arg) | Segment::Group(arg) => Ok(format!("{{{}}}", arg)),
+        })
+        .collect::<syn::Result<String>>()?;
+    Ok(LitStr::new(&value, format.span()))
+}
+
+
+/// Name of field in format string placeholders: raw identifier `r#type` is referenced as `{type}`
+fn field_name(ident: &Ident)->String{
+    let name = ident.to_string();
+    match name.strip_prefix("r#"){
+        Some(stripped) => stripped.to_string(),
+        None => name,
+    }
+}
+
+/// Is environment variable `REFORMATION_DEBUG=1` set during compilation
+fn debug_enabled()->bool{
+    std::env::var("REFORMATION_DEBUG").is_ok_and(|value| value == "1")
+}
+
+/// Regular expression of `String` field for `string_default` mode
+fn string_regex(mode: &str)->Option<&'static str>{
+    match mode{
+        "greedy" => Some("(.*)"),
+        "lazy" => Some("(.*?)"),
+        "non_ws" => Some(r"(\S+)"),
+        _ => None,
+    }
+}
+
+/// Compile time description of derived implementation: regular expression
+/// and placeholders with types.
+fn debug_description(name: &str, pattern: &str, placeholders: &[(String, String)])->String{
+    let mut res = format!("reformation: {}: {}\n", name, pattern);
+    for (placeholder, ty) in placeholders{
+        res.push_str(&format!("    {}: {}\n", placeholder, ty));
+    }
+    res
+}
+
+/// Regular expression of field as far as it is known to derive: literal regex is
+/// shown as is, and regex of type is only known at runtime, so it is shown as `<Type>`.
+fn debug_regex(field: &FieldCode)->String{
+    match syn::parse2::<LitStr>(field.regex.clone()){
+        Ok(lit) => lit.value(),
+        Err(_) => format!("<{}>", field.ty_name),
+    }
+}
+
+/// Template of `format!`, producing regular expression, with named arguments
+/// replaced by given regexes and escaped braces unescaped.
+fn expand_template(template: &str, args: &[(String, String)])->String{
+    let mut res = String::new();
+    let mut iter = template.chars().peekable();
+    while let Some(c) = iter.next(){
+        match c{
+            '{' if iter.peek() == Some(&'{') => {
+                iter.next();
+                res.push('{');
+            },
+            '}' if iter.peek() == Some(&'}') => {
+                iter.next();
+                res.push('}');
+            },
+            '{' => {
+                let arg: String = iter.by_ref().take_while(|c| *c != '}').collect();
+                match args.iter().find(|(name, _)| *name == arg){
+                    Some((_, regex)) => res.push_str(regex),
+                    None => res.push_str(&format!("{{{}}}", arg)),
+                }
+            },
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Regular expression of fields, printed when `REFORMATION_DEBUG=1` is set
+fn debug_pattern(template: &str, fields: &[FieldCode])->String{
+    let args: Vec<_> = fields.iter()
+        .filter(|f| !f.named_group)
+        .map(|f| (f.name.to_string(), debug_regex(f)))
+        .collect();
+    expand_template(template, &args)
+}
+
 
+/// Regular expressions of generic types are identified by `TypeId`, so type
+/// parameters of implementations, which parse value, must be `'static`.
 fn add_trait_bounds(generics: &mut Generics){
     for param in &mut generics.params {
         if let GenericParam::Type(ref mut type_param) = *param {
-            type_param.bounds.push(parse_quote!(::reformation::Reformation));
+            type_param.bounds.push(parse_quote!('static));
+        }
+    }
+}
+
+/// Require trait, used to parse field, from field types depending on type parameters:
+/// field of type `Option<T>` needs `Option<T>: Reformation` rather than `T: Reformation`.
+fn add_field_bounds(generics: &mut Generics, fields: &[FieldCode]){
+    let params: Vec<_> = generics.type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let bounds: Vec<_> = fields.iter()
+        .filter(|field| uses_type_params(field.ty, &params))
+        .filter_map(|field| field.bound.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for bound in bounds{
+        where_clause.predicates.push(parse_quote!(#bound));
+    }
+}
+
+fn uses_type_params(ty: &Type, params: &[Ident])->bool{
+    fn visit(tokens: TokenStream, params: &[Ident])->bool{
+        tokens.into_iter().any(|token| match token{
+            proc_macro2::TokenTree::Ident(ident) => params.contains(&ident),
+            proc_macro2::TokenTree::Group(group) => visit(group.stream(), params),
+            _ => false,
+        })
+    }
+    visit(quote!(#ty), params)
+}
+
+/// Is type generic. Statics inside of generic functions are shared between all
+/// instantiations, so generic types cannot store regular expressions in `lazy_static!`.
+fn is_generic(ds: &DeriveInput)->bool{
+    ds.generics.type_params().next().is_some()
+}
+
+/// Statement declaring variable `re` containing compiled regular expression of `Self`
+fn quote_regex(ds: &DeriveInput)->TokenStream{
+    let name = &ds.ident;
+    if is_generic(ds){
+        quote!{
+            let re = ::reformation::generic_regex::<Self, _>(|| <Self as ::reformation::Reformation>::regex_str().to_string())
+                .unwrap_or_else(|x| panic!("Cannot compile regex {:?}", x));
+        }
+    }else{
+        quote!{
+            ::reformation::lazy_static!{
+                static ref RE: ::reformation::Regex = {
+                    ::reformation::cached_regex(<#name as ::reformation::Reformation>::regex_str())
+                        .unwrap_or_else(|x| panic!("Cannot compile regex {:?}", x))
+                };
+            }
+            let re: &::reformation::Regex = &RE;
         }
     }
 }
 
 
+/// Arguments of all `#[reformation(..)]` attributes, merged into single parenthesized
+/// list, so options can be split between several attributes placed anywhere among
+/// other attributes. Arguments starting with format string go first.
+fn merged_re_parse_attributes(attrs: &[Attribute])->Option<TokenStream>{
+    let mut args: Vec<TokenStream> = attrs.iter()
+        .filter_map(get_re_parse_attribute)
+        .map(|tts|{
+            let mut iter = tts.clone().into_iter();
+            match (iter.next(), iter.next()){
+                (Some(proc_macro2::TokenTree::Group(group)), None)
+                    if group.delimiter() == proc_macro2::Delimiter::Parenthesis => group.stream(),
+                // malformed attribute is reported by parser
+                _ => tts.clone(),
+            }
+        })
+        .collect();
+    if args.is_empty(){
+        return None;
+    }
+    if args.len() == 1{
+        return attrs.iter().filter_map(get_re_parse_attribute).next().cloned();
+    }
+    args.sort_by_key(|args| match args.clone().into_iter().next(){
+        Some(proc_macro2::TokenTree::Literal(_)) => 0,
+        _ => 1,
+    });
+    Some(quote!{ ( #(#args),* ) })
+}
+
 fn get_re_parse_attribute(a: &Attribute)->Option<&TokenStream>{
     let pound = &a.pound_token;
     let path = &a.path;
@@ -74,181 +486,2909 @@ fn get_re_parse_attribute(a: &Attribute)->Option<&TokenStream>{
 }
 
 
-fn impl_from_str_body(re: Expr, ds: &DeriveInput)->Result<TokenStream, TokenStream>{
-    let re_str = get_regex_str(&re)?;
-    let args = arguments(&re_str);
+fn impl_from_str_body(attr: &StructAttribute, ds: &DeriveInput, discarded: &[(Ident, Type)])->Result<TokenStream, TokenStream>{
+    let re_str = attr.format_string();
+    // capture groups marking alternatives are not part of segments
+    let alternatives: Vec<_> = if attr.alternatives.is_empty(){
+        vec![segments(&re_str)]
+    }else{
+        attr.formats().map(|f| segments(&f.value())).collect()
+    };
+    let alternatives: Vec<_> = alternatives.into_iter().map(bind_named_groups).collect();
+    let args = arguments(alternatives.iter().flatten());
+    let groups: Vec<_> = alternatives.iter().flatten()
+        .filter_map(|s| match s{
+            Segment::Group(arg) => Some(arg.as_str()),
+            _ => None,
+        })
+        .collect();
+    let placeholders = alternatives.iter().flatten().any(|s| match s{
+        Segment::Field(arg) => groups.contains(&arg.as_str()),
+        _ => false,
+    });
+    if placeholders{
+        let msg = "Field bound to named group cannot appear in format string as placeholder.";
+        return Err(quote_spanned!{attr.format.span()=>
+            compile_error!{#msg}
+        });
+    }
     let fields = get_fields(&ds)?;
 
-    let (names, types): (Vec<_>, Vec<_>) = fields.iter()
-        .map(|x| (x.ident.as_ref().unwrap(), &x.ty))
-        .filter(|(ident, _ty)| args.contains(&ident.to_string()))
-        .unzip();
+    // fields receiving whole match or default value do not appear in format string
+    let mut extra = vec![];
+    for field in &fields{
+        let name = field.ident.as_ref().unwrap();
+        let field_attr = FieldAttribute::from_field(field)
+            .map_err(|e| e.to_compile_error())?;
+        if field_attr.capture_whole{
+            if args.iter().any(|arg| field_name(name) == *arg){
+                let msg = format!("Field `{}` receiving whole match cannot appear in format string.", name);
+                return Err(quote_spanned!{attr.format.span()=>
+                    compile_error!{#msg}
+                });
+            }
+            extra.push((name, quote!{ ::std::convert::From::from(captures.get(0).map(|x| x.as_str()).unwrap_or("")) }));
+        }else if let Some(expr) = field_attr.default{
+            if !args.iter().any(|arg| field_name(name) == *arg){
+                extra.push((name, quote!{ #expr }));
+            }
+        }
+    }
 
-    // hack over unability of quote to use same variable multiple times
+    // `String` fields without attributes changing their regular expression
+    let mut plain_strings = vec![];
+    // Capture groups are numbered in order of appearance in format string,
+    // so fields must be parsed in the same order.
+    let mut fields = args.iter()
+        .map(|arg|{
+            if let Some((ident, ty)) = discarded.iter().find(|(ident, _)| ident == arg){
+                let mut code = FieldCode::plain(ident, ty);
+                // value inside of optional group, which did not participate in match, is not parsed
+                let count = &code.count;
+                let parse = &code.parse;
+                code.parse = quote!{{
+                    let count = #count;
+                    if (offset..offset + count).any(|i| captures.get(i).is_some()){
+                        let (_, offset) = #parse;
+                        ((), offset)
+                    }else{
+                        ((), offset + count)
+                    }
+                }};
+                code.discarded = true;
+                return Ok(code);
+            }
+            let field = fields.iter()
+                .find(|x| field_name(x.ident.as_ref().unwrap()) == *arg)
+                .ok_or_else(||{
+                    let msg = format!("Format string references unknown field `{}`.", arg);
+                    quote_spanned!{attr.format.span()=>
+                        compile_error!{#msg}
+                    }
+                })?;
+            let mut code = FieldCode::new(field, field.ident.as_ref().unwrap())?;
+            if groups.contains(arg){
+                code.bind_to_group();
+            }
+            if has_string_regex(field){
+                plain_strings.push((code.name, option_inner(&field.ty).is_some()));
+            }
+            Ok(code)
+        })
+        .collect::<Result<Vec<_>, TokenStream>>()?;
+    if !attr.greedy{
+        for field in &mut fields{
+            let regex = &field.regex;
+            field.regex = quote!{ &::reformation::lazy_quantifiers(#regex) };
+        }
+    }
+    apply_string_default(attr, &mut fields, &plain_strings);
 
-    let generics = &ds.generics;
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let name = &ds.ident;
-    let re_parse_body = quote_impl_reformation(&re_str, &names, &types);
-    let from_str_body = quote_impl_from_str(&ds);
+    for (segments, format) in alternatives.iter().zip(attr.formats()){
+        let missing = args.iter()
+            .filter(|arg| !discarded.iter().any(|(ident, _)| ident == *arg))
+            .find(|arg| !arguments(segments).contains(arg));
+        if let Some(arg) = missing{
+            let msg = format!("Format alternative does not reference field `{}`.", arg);
+            return Err(quote_spanned!{format.span()=>
+                compile_error!{#msg}
+            });
+        }
+    }
+
+    for field in &fields{
+        let occurrences = alternatives.iter()
+            .map(|segments| segments.iter()
+                .filter(|s| match s{
+                    Segment::Field(arg) | Segment::Group(arg) => field_name(field.name) == *arg,
+                    _ => false,
+                })
+                .count()
+            )
+            .max()
+            .unwrap_or(0);
+        if occurrences > 1 && !field.reuse{
+            let msg = format!(
+                "Field `{}` appears in format string multiple times. Mark it with `#[reformation(reuse)]` to require all occurrences to be equal.",
+                field.name
+            );
+            return Err(quote_spanned!{attr.format.span()=>
+                compile_error!{#msg}
+            });
+        }
+    }
 
+    // `canonical` only renders value, so it does not need bounds of parsing
+    let canonical_ds = ds;
+    let mut generics = ds.generics.clone();
+    add_trait_bounds(&mut generics);
+    add_field_bounds(&mut generics, &fields);
+    let ds = &DeriveInput{generics, ..ds.clone()};
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let name = &ds.ident;
+    let pattern = if attr.verbose_pattern{
+        verbose_format(&re_str, &fields)
+    }else{
+        re_str.clone()
+    };
+    if debug_enabled(){
+        let placeholders: Vec<_> = fields.iter()
+            .map(|f|{
+                let placeholder = if f.discarded{
+                    "{_}".to_string()
+                }else if f.named_group{
+                    format!("(?P<{}>)", field_name(f.name))
+                }else{
+                    format!("{{{}}}", field_name(f.name))
+                };
+                (placeholder, f.ty_name.clone())
+            })
+            .collect();
+        eprintln!("{}", debug_description(&name.to_string(), &debug_pattern(&pattern, &fields), &placeholders));
+    }
+    let re_parse_body = quote_impl_reformation(&pattern, &alternatives, &fields, &extra, is_generic(ds), attr.unicode);
+    let explain_body = quote_explain(&name.to_string(), attr, &alternatives, &fields);
+    let min_len = format_min_len(&re_str);
+    let from_str_body = quote_impl_from_str(ds, attr.error.as_ref(), attr.try_from);
+    let parse_opt_body = quote_parse_opt(ds);
+    let try_parse_ref_body = if attr.try_parse_ref{
+        quote_try_parse_ref(ds)
+    }else{
+        quote!{}
+    };
+    let is_body = if attr.is{
+        quote_is(ds)
+    }else{
+        quote!{}
+    };
+    let nom_body = if attr.nom{
+        quote_parse_nom(ds)
+    }else{
+        quote!{}
+    };
+    if attr.canonical && !discarded.is_empty(){
+        return Err(quote_spanned!{attr.format.span()=>
+            compile_error!{"Discarded placeholder cannot be rendered by `canonical`."}
+        });
+    }
+    if attr.reparse && alternatives.len() > 1{
+        return Err(quote_spanned!{attr.format.span()=>
+            compile_error!{"Argument `reparse` cannot be combined with alternative formats."}
+        });
+    }
+    let reparse_body = if attr.reparse{
+        quote_reparse(ds, &alternatives[0], &fields, &extra)?
+    }else{
+        quote!{}
+    };
+    let canonical_body = if attr.canonical{
+        quote_canonical(canonical_ds, &segments(&attr.first_format_string()))
+            .map_err(|msg| quote_spanned!{attr.format.span()=>
+                compile_error!{#msg}
+            })?
+    }else{
+        quote!{}
+    };
 
     Ok(quote!{
         impl #impl_generics ::reformation::Reformation for #name #ty_generics #where_clause{
             #re_parse_body
+
+            #parse_opt_body
+
+            #explain_body
+
+            fn min_len()->usize{
+                #min_len
+            }
         }
 
         #from_str_body
+
+        #try_parse_ref_body
+
+        #is_body
+
+        #nom_body
+
+        #reparse_body
+
+        #canonical_body
     })
 }
 
-fn quote_impl_reformation(re_str: &str, names: &[&Ident], types: &[&Type])->TokenStream{
-    let types1 = types;
-    let types2 = types;
-    let types3 = types;
-    let types4 = types;
+/// Implementation for `raw` format: regular expression is used as is, and each
+/// field is parsed from capture group with the same name.
+fn impl_raw_body(attr: &StructAttribute, ds: &DeriveInput)->Result<TokenStream, TokenStream>{
+    let error = |msg: String| quote_spanned!{attr.format.span()=>
+        compile_error!{#msg}
+    };
+    if !attr.alternatives.is_empty() || attr.canonical || attr.reparse{
+        return Err(error("Argument `raw` cannot be combined with alternative formats, `canonical` or `reparse`.".to_string()));
+    }
+    if attr.string_default.is_some(){
+        return Err(error("Argument `raw` cannot be combined with `string_default`, fields are matched by named groups.".to_string()));
+    }
+    if attr.word_start || attr.word_end{
+        return Err(error("Argument `raw` cannot be combined with word boundaries, use `\\b` in regular expression instead.".to_string()));
+    }
+    if attr.verbose_pattern{
+        return Err(error("Argument `raw` cannot be combined with `verbose_pattern`, use `(?x)` flag in regular expression instead.".to_string()));
+    }
+    if !attr.unicode{
+        return Err(error("Argument `raw` cannot be combined with `unicode = false`, use `(?-u)` flag in regular expression instead.".to_string()));
+    }
+    let literal = |s: &Option<LitStr>| s.as_ref().map(|s| escape_regex(&s.value())).unwrap_or_default();
+    let re_str = format!("{}{}{}", literal(&attr.prefix), attr.format.value(), literal(&attr.suffix));
+    let min_len = regex_min_len(&mut re_str.chars().peekable());
 
-    let names1 = names;
-    let names2 = names;
-    let names3 = names;
-    quote!{
-        fn regex_str()->&'static str{
-            ::reformation::lazy_static!{
-                static ref STR: String = {
-                    format!(#re_str, #(#names1 = <#types1 as ::reformation::Reformation>::regex_str()),*)
-                };
-            }
-            &STR
+    let mut extra = vec![];
+    // `Option<T>` field is parsed as `T` if its group participated in match
+    let mut bound_fields = vec![];
+    for field in get_fields(ds)?{
+        let name = field.ident.as_ref().unwrap();
+        let field_attr = FieldAttribute::from_field(field)
+            .map_err(|e| e.to_compile_error())?;
+        if field_attr.capture_whole{
+            extra.push((name, quote!{ ::std::convert::From::from(captures.get(0).map(|x| x.as_str()).unwrap_or("")) }));
+            continue;
         }
-
-        fn captures_count()->usize{
-            let mut count = 0;
-            #(count += <#types2 as ::reformation::Reformation>::captures_count();)*
-            count
+        let has_group = re_str.contains(&format!("(?P<{}>", field_name(name)))
+            || re_str.contains(&format!("(?<{}>", field_name(name)));
+        if !has_group{
+            if let Some(expr) = field_attr.default{
+                extra.push((name, quote!{ #expr }));
+                continue;
+            }
+            return Err(error(format!("Raw regex does not contain named group `{}`.", name)));
         }
-
-        fn from_captures(captures: &::reformation::Captures, mut offset: usize)->Result<Self, Box<std::error::Error>>{
-            #(
-                let #names2 = <#types3 as ::reformation::Reformation>::from_captures(&captures, offset)?;
-                offset += <#types4 as ::reformation::Reformation>::captures_count();
-            )*
-            Ok(Self{
-                #(#names3,)*
-            })
+        match option_inner(&field.ty){
+            Some(inner) => bound_fields.push((Field{ty: inner.clone(), ..field.clone()}, true)),
+            None => bound_fields.push((field.clone(), false)),
         }
     }
-}
+    let fields = bound_fields.iter()
+        .map(|(field, _)| FieldCode::new(field, field.ident.as_ref().unwrap()))
+        .collect::<Result<Vec<_>, _>>()?;
 
-fn quote_impl_from_str(ds: &DeriveInput)->TokenStream{
+    let mut generics = ds.generics.clone();
+    add_trait_bounds(&mut generics);
+    add_field_bounds(&mut generics, &fields);
+    let ds = &DeriveInput{generics, ..ds.clone()};
     let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
-    let ty_generics2 = &ty_generics;
     let name = &ds.ident;
-    let name2 = &ds.ident;
-    quote!{
+    if debug_enabled(){
+        let placeholders: Vec<_> = bound_fields.iter()
+            .map(|(field, _)| (format!("(?P<{}>)", field.ident.as_ref().unwrap()), type_name(&field.ty)))
+            .collect();
+        eprintln!("{}", debug_description(&name.to_string(), &re_str, &placeholders));
+    }
+    let re = quote_regex(ds);
+    let names = fields.iter().map(|f| f.name);
+    let group_names = fields.iter().map(|f| field_name(f.name));
+    let parses = fields.iter().zip(&bound_fields).map(|(f, (_, optional))|{
+        let parse = &f.parse;
+        if *optional{
+            quote!{
+                if captures.get(offset).is_some(){
+                    let (value, offset) = #parse;
+                    (Some(value), offset)
+                }else{
+                    (None, offset)
+                }
+            }
+        }else{
+            parse.clone()
+        }
+    });
+    let names2 = fields.iter().map(|f| f.name);
+    let extra_names = extra.iter().map(|(name, _)| name);
+    let extra_values = extra.iter().map(|(_, value)| value);
+    let from_str_body = quote_impl_from_str(ds, attr.error.as_ref(), attr.try_from);
+    let parse_opt_body = quote_parse_opt(ds);
+    let try_parse_ref_body = if attr.try_parse_ref{
+        quote_try_parse_ref(ds)
+    }else{
+        quote!{}
+    };
+    let is_body = if attr.is{
+        quote_is(ds)
+    }else{
+        quote!{}
+    };
+    let nom_body = if attr.nom{
+        quote_parse_nom(ds)
+    }else{
+        quote!{}
+    };
 
-        impl #impl_generics std::str::FromStr for #name #ty_generics #where_clause{
-            type Err = Box<std::error::Error>;
+    Ok(quote!{
+        impl #impl_generics ::reformation::Reformation for #name #ty_generics #where_clause{
+            fn regex_str()->&'static str{
+                #re_str
+            }
 
-            fn from_str(input_str: &str)->Result<Self, Self::Err>{
-                reformation::lazy_static!{
-                    static ref RE: reformation::Regex = {
-                        reformation::Regex::new(#name2 #ty_generics2::regex_str())
-                            .unwrap_or_else(|x| panic!("Cannot compile regex {:?}", ))
-                    };
-                }
+            fn captures_count()->usize{
+                #re
+                re.captures_len() - 1
+            }
 
-                let captures = RE.captures(input_str).ok_or_else(||{
-                        ::reformation::NoRegexMatch{
-                            format: Self::regex_str(),
-                            request: input_str.to_string()
-                        }
-                    })?;
-                Self::from_captures(&captures, 1)
+            fn min_len()->usize{
+                #min_len
+            }
+
+            #parse_opt_body
+
+            fn from_captures(captures: &::reformation::Captures, offset: usize)->Result<Self, Box<dyn ::std::error::Error>>{
+                Self::from_captures_consuming(captures, offset).map(|(value, _)| value)
+            }
+
+            fn from_captures_consuming(captures: &::reformation::Captures, offset: usize)
+                ->Result<(Self, usize), Box<dyn ::std::error::Error>>
+            {
+                #re
+                #(
+                    let (#names, _) = {
+                        let index = re.capture_names()
+                            .position(|name| name == Some(#group_names))
+                            .unwrap();
+                        let offset = offset + index - 1;
+                        #parses
+                    };
+                )*
+                Ok((Self{
+                    #(#names2,)*
+                    #(#extra_names: #extra_values,)*
+                }, offset + re.captures_len() - 1))
             }
         }
 
-    }
-}
+        #from_str_body
 
+        #try_parse_ref_body
 
-fn get_fields(struct_: &DeriveInput)->Result<Vec<&Field>, TokenStream>{
-    if let Data::Struct(ref ds) = struct_.data{
-        let fields: Vec<_> = ds.fields.iter().collect();
+        #is_body
 
-        if let Fields::Named(_) = ds.fields{
-            Ok(fields)
-        }else{
-            Err(quote_spanned!{ds.fields.span()=>
-                compile_error!{"regex_parse supports only structs with named fields."}
-            })
+        #nom_body
+    })
+}
+
+/// Implementation of `ReformationRef`: inherent methods `regex_str` and `parse`,
+/// which borrows `&str` fields from input. Other fields are parsed as by `Reformation`.
+fn impl_ref_body(attr: &StructAttribute, ds: &DeriveInput)->Result<TokenStream, TokenStream>{
+    let error = |msg: String| quote_spanned!{attr.format.span()=>
+        compile_error!{#msg}
+    };
+    let unsupported = [
+        (!attr.alternatives.is_empty(), "alternative formats"),
+        (attr.error.is_some(), "error"),
+        (attr.try_parse_ref, "try_parse_ref"),
+        (attr.is, "is"),
+        (attr.nom, "nom"),
+        (attr.canonical, "canonical"),
+        (attr.raw, "raw"),
+        (attr.verbose_pattern, "verbose_pattern"),
+        (attr.reparse, "reparse"),
+        (attr.try_from, "try_from"),
+    ];
+    if let Some((_, arg)) = unsupported.iter().find(|(used, _)| *used){
+        return Err(error(format!("Argument `{}` is not supported by `ReformationRef`.", arg)));
+    }
+    if is_generic(ds){
+        return Err(error("`ReformationRef` supports only lifetime parameters.".to_string()));
+    }
+
+    let re_str = attr.format_string();
+    let segments = segments(&re_str);
+    let args = arguments(&segments);
+    let struct_fields = get_fields(ds)?;
+    for field in &struct_fields{
+        let name = field.ident.as_ref().unwrap();
+        if !args.iter().any(|arg| field_name(name) == *arg){
+            return Err(error(format!("Format string does not reference field `{}`.", name)));
         }
-    }else{
-        Err(quote_spanned!{struct_.span()=>
-            compile_error!{"regex_parse supports only structs."}
+    }
+    let mut plain_strings = vec![];
+    let mut fields = args.iter()
+        .map(|arg|{
+            let field = struct_fields.iter()
+                .find(|x| field_name(x.ident.as_ref().unwrap()) == *arg)
+                .ok_or_else(|| error(format!("Format string references unknown field `{}`.", arg)))?;
+            let name = field.ident.as_ref().unwrap();
+            let borrowed = if is_str_ref(&field.ty){
+                Some(false)
+            }else{
+                option_inner(&field.ty).filter(|ty| is_str_ref(ty)).map(|_| true)
+            };
+            // borrowed optional slice is matched by regex of `String`, rather than of `Option`
+            if borrowed.is_some(){
+                plain_strings.push((name, false));
+            }else if has_string_regex(field){
+                plain_strings.push((name, option_inner(&field.ty).is_some()));
+            }
+            match borrowed{
+                Some(optional) => {
+                    if merged_re_parse_attributes(&field.attrs).is_some(){
+                        return Err(error(format!("Borrowed field `{}` cannot have field attributes.", name)));
+                    }
+                    Ok(FieldCode::borrowed(name, &field.ty, optional))
+                },
+                None => FieldCode::new(field, name),
+            }
         })
+        .collect::<Result<Vec<_>, TokenStream>>()?;
+    if !attr.greedy{
+        for field in &mut fields{
+            let regex = &field.regex;
+            field.regex = quote!{ &::reformation::lazy_quantifiers(#regex) };
+        }
+    }
+    apply_string_default(attr, &mut fields, &plain_strings);
+    for field in &fields{
+        let occurrences = segments.iter()
+            .filter(|s| match s{
+                Segment::Field(arg) => field_name(field.name) == *arg,
+                _ => false,
+            })
+            .count();
+        if occurrences > 1 && !field.reuse{
+            let msg = format!(
+                "Field `{}` appears in format string multiple times. Mark it with `#[reformation(reuse)]` to require all occurrences to be equal.",
+                field.name
+            );
+            return Err(error(msg));
+        }
     }
-}
 
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let name = &ds.ident;
+    let lifetime = ds.generics.lifetimes().next()
+        .map(|def| def.lifetime.clone())
+        .unwrap_or_else(|| syn::Lifetime::new("'static", proc_macro2::Span::call_site()));
+    let (_, parses) = quote_alternative(&segments, &fields);
+    let names1 = fields.iter().map(|f| f.name);
+    let regexes = fields.iter().map(|f| &f.regex);
+    let names2 = fields.iter().map(|f| f.name);
+    let mut regex = quote!{ format!(#re_str, #(#names1 = #regexes),*) };
+    if !attr.unicode{
+        regex = quote!{ ::reformation::ascii_regex(&#regex) };
+    }
 
-fn get_regex_str(re: &Expr)->Result<String, TokenStream>{
-    expr_par(re)
-        .and_then(expr_lit)
-        .and_then(lit_str)
-        .ok_or_else(||{
-            quote_spanned!{re.span()=>
-                compile_error!{"regex_parse argument must be string literal."}
+    Ok(quote!{
+        impl #impl_generics #name #ty_generics #where_clause{
+            /// Regular expression matching value, as used by `parse`.
+            pub fn regex_str()->&'static str{
+                ::reformation::lazy_static!{
+                    static ref STR: String = {
+                        #regex
+                    };
+                }
+                &STR
             }
-        })
+
+            /// Parse value, borrowing string fields from input.
+            pub fn parse(input_str: &#lifetime str)->Result<Self, Box<dyn ::std::error::Error>>{
+                ::reformation::lazy_static!{
+                    static ref RE: ::reformation::Regex = {
+                        ::reformation::cached_regex(#name::regex_str())
+                            .unwrap_or_else(|x| panic!("Cannot compile regex {:?}", x))
+                    };
+                }
+                let captures = RE.captures(input_str).ok_or_else(||{
+                    ::reformation::NoRegexMatch{
+                        format: #name::regex_str(),
+                        request: input_str.to_string(),
+                    }
+                })?;
+                let offset = 1;
+                #(#parses)*
+                let _ = offset;
+                Ok(Self{
+                    #(#names2,)*
+                })
+            }
+        }
+    })
 }
 
-fn expr_par(x: &Expr)->Option<&Expr>{
-    if let Expr::Paren(ref i) = x{
-        Some(i.expr.deref())
-    }else{
-        None
+/// Is type `&str` with any lifetime
+fn is_str_ref(ty: &Type)->bool{
+    match ty{
+        Type::Reference(reference) if reference.mutability.is_none() => match &*reference.elem{
+            Type::Path(path) => path.qself.is_none() && path.path.is_ident("str"),
+            _ => false,
+        },
+        _ => false,
     }
 }
 
-fn expr_lit(x: &Expr)->Option<&Lit>{
-    if let Expr::Lit(ref i) = x{
-        Some(&i.lit)
-    }else{
-        None
+/// Value of `default = "expr"` argument. String literal is converted into type of field,
+/// so `default = "\"none\""` can be used for `String` field.
+fn default_expr(expr: syn::Expr)->syn::Expr{
+    match &expr{
+        syn::Expr::Lit(lit) => match &lit.lit{
+            syn::Lit::Str(_) => syn::parse_quote!{ ::std::convert::From::from(#expr) },
+            _ => expr,
+        },
+        _ => expr,
     }
 }
 
-fn lit_str(x: &Lit)->Option<String>{
-    if let Lit::Str(ref s) = x{
-        Some(s.value())
-    }else{
-        None
+/// `T` if type is `Option<T>`
+fn option_inner(ty: &Type)->Option<&Type>{
+    let path = match ty{
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.iter().last()?;
+    if segment.ident != "Option"{
+        return None;
+    }
+    match &segment.arguments{
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0]{
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
+fn quote_impl_reformation(re_str: &str, alternatives: &[Vec<Segment>], fields: &[FieldCode], extra: &[(&Ident, TokenStream)], generic: bool, unicode: bool)->TokenStream{
+    // hack over unability of quote to use same variable multiple times
+    let names2 = fields.iter().filter(|f| !f.discarded).map(|f| f.name);
+    // fields bound to named groups do not have placeholders
+    let placeholders: Vec<_> = fields.iter().filter(|f| !f.named_group).collect();
+    let names1 = placeholders.iter().map(|f| f.name);
+    let regexes = placeholders.iter().map(|f| &f.regex);
+    let extra_names = extra.iter().map(|(name, _)| name);
+    let extra_values = extra.iter().map(|(_, value)| value);
+    let construct = quote!{
+        Self{
+            #(#names2,)*
+            #(#extra_names: #extra_values,)*
+        }
+    };
 
-/// parse which fields present in format string
-fn arguments(format_string: &str)->HashSet<String>{
-    let mut curly_bracket_stack = vec![];
-    let mut map = HashSet::new();
-
-    let mut iter = format_string.char_indices().peekable();
-    loop{
-        match iter.next(){
-            Some((i, c)) if c == '{' => {
-                if iter.peek().map(|(_, c)| *c) != Some('{'){
-                    curly_bracket_stack.push(i + c.len_utf8());
+    let (counts, from_captures) = if alternatives.len() == 1{
+        let (counts, parses) = quote_alternative(&alternatives[0], fields);
+        let from_captures = quote!{
+            #(#parses)*
+            Ok((#construct, offset))
+        };
+        (counts, from_captures)
+    }else{
+        let mut all_counts = vec![];
+        let alternatives = alternatives.iter().map(|segments|{
+            let (counts, parses) = quote_alternative(segments, fields);
+            let counts2 = counts.clone();
+            all_counts.push(quote!{ 1 });
+            all_counts.extend(counts);
+            quote!{
+                if captures.get(offset).is_some(){
+                    let offset = offset + 1;
+                    #(#parses)*
+                    let _ = offset;
+                    return Ok((#construct, end));
+                }
+                let offset = offset + 1 #(+ #counts2)*;
+            }
+        }).collect::<Vec<_>>();
+        let from_captures = quote!{
+            let end = offset + <Self as ::reformation::Reformation>::captures_count();
+            #(#alternatives)*
+            let _ = offset;
+            Err("Input does not match any of format alternatives".into())
+        };
+        (all_counts, from_captures)
+    };
+
+    let mut regex = quote!{ format!(#re_str, #(#names1 = #regexes),*) };
+    if !unicode{
+        regex = quote!{ ::reformation::ascii_regex(&#regex) };
+    }
+    let regex_str = if generic{
+        quote!{
+            ::reformation::generic_regex_str::<Self, _>(||{
+                #regex
+            })
+        }
+    }else{
+        quote!{
+            ::reformation::lazy_static!{
+                static ref STR: String = {
+                    #regex
+                };
+            }
+            &STR
+        }
+    };
+
+    quote!{
+        fn regex_str()->&'static str{
+            #regex_str
+        }
+
+        fn captures_count()->usize{
+            let mut count = 0;
+            #(count += #counts;)*
+            count
+        }
+
+        fn from_captures(captures: &::reformation::Captures, offset: usize)->Result<Self, Box<dyn ::std::error::Error>>{
+            Self::from_captures_consuming(captures, offset).map(|(value, _)| value)
+        }
+
+        fn from_captures_consuming(captures: &::reformation::Captures, offset: usize)
+            ->Result<(Self, usize), Box<dyn ::std::error::Error>>
+        {
+            #from_captures
+        }
+    }
+}
+
+/// Captures counts and statements parsing fields of single format alternative,
+/// in order of their appearance in format string.
+fn quote_alternative(segments: &[Segment], fields: &[FieldCode])->(Vec<TokenStream>, Vec<TokenStream>){
+    let occurrences: Vec<_> = segments.iter()
+        .filter_map(|s| match s{
+            Segment::Field(arg) | Segment::Group(arg) => fields.iter().find(|f| field_name(f.name) == *arg),
+            _ => None,
+        })
+        .collect();
+    let counts = occurrences.iter().map(|f| f.count.clone()).collect();
+    let parses = occurrences.iter().enumerate().map(|(i, f)|{
+        let name = f.name;
+        let parse = &f.parse;
+        if occurrences[..i].iter().any(|x| x.name == name){
+            let msg = format!("Field `{}` has different values in different places of input.", name);
+            quote!{
+                let offset = {
+                    let (value, offset) = #parse;
+                    if value != #name{
+                        return Err(#msg.into());
+                    }
+                    offset
+                };
+            }
+        }else{
+            quote!{
+                let (#name, offset) = #parse;
+            }
+        }
+    }).collect();
+    (counts, parses)
+}
+
+fn quote_explain(name: &str, attr: &StructAttribute, alternatives: &[Vec<Segment>], fields: &[FieldCode])->TokenStream{
+    let lines = if alternatives.len() == 1{
+        quote_explain_segments(&alternatives[0], fields)
+    }else{
+        let literal = |s: &Option<LitStr>|{
+            s.as_ref().map(|s| quote_explain_segments(&[Segment::Literal(escape_regex(&s.value()))], fields))
+        };
+        let prefix = literal(&attr.prefix);
+        let suffix = literal(&attr.suffix);
+        let alternatives = alternatives.iter().enumerate().map(|(i, segments)|{
+            let lines = quote_explain_segments(segments, fields);
+            quote!{
+                res.push_str(&format!("    alternative {}: group {}\n", #i, offset));
+                offset += 1;
+                #lines
+            }
+        });
+        quote!{
+            #prefix
+            #(#alternatives)*
+            #suffix
+        }
+    };
+    quote!{
+        fn explain()->String{
+            let mut res = format!("{}: r{:?}\n", #name, <Self as ::reformation::Reformation>::regex_str());
+            let mut offset = 1;
+            #lines
+            let _ = offset;
+            res
+        }
+    }
+}
+
+fn quote_explain_segments(segments: &[Segment], fields: &[FieldCode])->TokenStream{
+    let lines = segments.iter().map(|segment|{
+        match segment{
+            Segment::Literal(literal) => quote!{
+                res.push_str(&format!("    literal r{:?}\n", #literal));
+            },
+            Segment::Field(arg) => {
+                let field = fields.iter().find(|f| field_name(f.name) == *arg).unwrap();
+                let arg = if field.discarded{ "_" }else{ arg };
+                let ty = &field.ty_name;
+                let regex = &field.regex;
+                let count = &field.count;
+                quote!{{
+                    let count = #count;
+                    res.push_str(&format!(
+                        "    {{{}}}: {} = r{:?}, groups {}..{}\n",
+                        #arg, #ty, #regex, offset, offset + count
+                    ));
+                    offset += count;
+                }}
+            },
+            Segment::Group(arg) => {
+                let field = fields.iter().find(|f| field_name(f.name) == *arg).unwrap();
+                let ty = &field.ty_name;
+                quote!{
+                    res.push_str(&format!("    (?P<{}>): {}, group {}\n", #arg, #ty, offset));
+                    offset += 1;
+                }
+            },
+        }
+    });
+    quote!{
+        #(#lines)*
+    }
+}
+
+fn quote_impl_from_str(ds: &DeriveInput, error: Option<&Type>, try_from: bool)->TokenStream{
+    let re = quote_regex(ds);
+    let body = quote!{
+        #re
+
+        let captures = re.captures(input_str).ok_or_else(||{
+                <Self::Err as ::reformation::ParseError>::no_match(<Self as ::reformation::Reformation>::regex_str(), input_str)
+            })?;
+        <Self as ::reformation::Reformation>::from_captures(&captures, 1).map_err(|e|{
+            <Self::Err as ::reformation::ParseError>::invalid_value(<Self as ::reformation::Reformation>::regex_str(), input_str, e)
+        })
+    };
+    quote_impl_from_str_with(ds, error, body, try_from)
+}
+
+/// `FromStr` implementation, parsing `input_str` with given body, and
+/// `TryFrom` implementations for string types if `try_from` is set
+fn quote_impl_from_str_with(ds: &DeriveInput, error: Option<&Type>, body: TokenStream, try_from: bool)->TokenStream{
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let error = error.cloned()
+        .unwrap_or_else(|| parse_quote!(Box<dyn ::std::error::Error>));
+    let name = &ds.ident;
+    let try_from_body = if try_from{
+        quote!{
+            impl #impl_generics ::std::convert::TryFrom<&str> for #name #ty_generics #where_clause{
+                type Error = #error;
+
+                fn try_from(input_str: &str)->Result<Self, Self::Error>{
+                    <Self as ::std::str::FromStr>::from_str(input_str)
+                }
+            }
+
+            impl #impl_generics ::std::convert::TryFrom<String> for #name #ty_generics #where_clause{
+                type Error = #error;
+
+                fn try_from(input_str: String)->Result<Self, Self::Error>{
+                    <Self as ::std::str::FromStr>::from_str(&input_str)
+                }
+            }
+
+            impl #impl_generics ::std::convert::TryFrom<&String> for #name #ty_generics #where_clause{
+                type Error = #error;
+
+                fn try_from(input_str: &String)->Result<Self, Self::Error>{
+                    <Self as ::std::str::FromStr>::from_str(input_str)
+                }
+            }
+        }
+    }else{
+        quote!{}
+    };
+    quote!{
+
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause{
+            type Err = #error;
+
+            fn from_str(input_str: &str)->Result<Self, Self::Err>{
+                #body
+            }
+        }
+
+        #try_from_body
+    }
+}
+
+fn quote_try_parse_ref(ds: &DeriveInput)->TokenStream{
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let name = &ds.ident;
+    let re = quote_regex(ds);
+    quote!{
+        impl #impl_generics #name #ty_generics #where_clause{
+            /// Parse string, reporting failure without allocation. Input, which matches
+            /// but contains invalid value, allocates while parsing its fields.
+            pub fn try_parse_ref(input_str: &str)->Result<Self, ::reformation::NoRegexMatchRef>{
+                #re
+                let error = ::reformation::NoRegexMatchRef{
+                    format: <Self as ::reformation::Reformation>::regex_str(),
+                    request: input_str,
+                };
+                // unlike `captures`, `is_match` does not allocate capture slots
+                if !re.is_match(input_str){
+                    return Err(error);
+                }
+                let captures = re.captures(input_str).ok_or(error)?;
+                <Self as ::reformation::Reformation>::from_captures(&captures, 1).map_err(|_| error)
+            }
+        }
+    }
+}
+
+
+/// Method `reparse`, assigning parsed fields to existing value. `String` fields without
+/// attributes changing their parsing are copied into existing buffers.
+fn quote_reparse(ds: &DeriveInput, segments: &[Segment], fields: &[FieldCode], extra: &[(&Ident, TokenStream)])->Result<TokenStream, TokenStream>{
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let name = &ds.ident;
+    let re = quote_regex(ds);
+    let struct_fields = get_fields(ds)?;
+    let mut buffers = vec![];
+    let fields: Vec<_> = fields.iter()
+        .map(|code|{
+            let field = struct_fields.iter().find(|f| f.ident.as_ref() == Some(code.name));
+            match field{
+                Some(field) if !code.discarded && reuses_buffer(field) => {
+                    buffers.push(code.name);
+                    let mut borrowed = FieldCode::borrowed(code.name, code.ty, false);
+                    borrowed.reuse = code.reuse;
+                    if code.named_group{
+                        borrowed.bind_to_group();
+                    }
+                    borrowed
+                },
+                _ => code.clone(),
+            }
+        })
+        .collect();
+    let (_, parses) = quote_alternative(segments, &fields);
+    let extra_names = extra.iter().map(|(name, _)| name);
+    let extra_values = extra.iter().map(|(_, value)| value);
+    let assigns = fields.iter()
+        .filter(|f| !f.discarded)
+        .map(|f|{
+            let name = f.name;
+            if buffers.contains(&name){
+                quote!{
+                    self.#name.clear();
+                    self.#name.push_str(#name);
+                }
+            }else{
+                quote!{ self.#name = #name; }
+            }
+        });
+    Ok(quote!{
+        impl #impl_generics #name #ty_generics #where_clause{
+            /// Parse string into existing value, reusing buffers of its `String` fields.
+            /// Value is left unchanged if parsing fails.
+            pub fn reparse(&mut self, input_str: &str)->Result<(), Box<dyn ::std::error::Error>>{
+                #re
+                let captures = re.captures(input_str).ok_or_else(||{
+                    ::reformation::NoRegexMatch{
+                        format: <Self as ::reformation::Reformation>::regex_str(),
+                        request: input_str.to_string(),
+                    }
+                })?;
+                let offset = 1;
+                #(#parses)*
+                let _ = offset;
+                #(#assigns)*
+                #(self.#extra_names = #extra_values;)*
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Is field `String` parsed from single capture group as is, so it can be copied
+/// into existing buffer
+fn reuses_buffer(field: &Field)->bool{
+    let attr = match FieldAttribute::from_field(field){
+        Ok(attr) => attr,
+        Err(_) => return false,
+    };
+    is_string(&field.ty) && !attr.transforms_str() && attr.flag.is_none() && attr.count.is_none()
+        && attr.separator.is_none() && attr.validate.is_none() && attr.when.is_none()
+        && !attr.default_if_absent && attr.default.is_none() && attr.range.is_none() && attr.digits.is_none()
+}
+
+/// Is type `String`
+fn is_string(ty: &Type)->bool{
+    match ty{
+        Type::Path(path) => path.qself.is_none() && path.path.is_ident("String"),
+        _ => false,
+    }
+}
+
+/// Is field `String` or `Option<String>` matched by default regular expression of `String`,
+/// which can be changed with `string_default`
+fn has_string_regex(field: &Field)->bool{
+    let is_string = is_string(&field.ty) || option_inner(&field.ty).is_some_and(is_string);
+    let attr = match FieldAttribute::from_field(field){
+        Ok(attr) => attr,
+        Err(_) => return false,
+    };
+    is_string && !attr.transforms_str() && attr.flag.is_none() && attr.count.is_none()
+        && attr.separator.is_none() && attr.max_len.is_none() && attr.count_of.is_none()
+}
+
+/// Replace regular expression of string fields by the one of `string_default` mode.
+/// Fields are given by name and whether regex is wrapped into optional group, as
+/// `Reformation` impl of `Option<String>` does.
+/// Explicit `string_default` takes precedence over `greedy = false`.
+fn apply_string_default(attr: &StructAttribute, fields: &mut [FieldCode], strings: &[(&Ident, bool)]){
+    let regex = match attr.string_default.as_ref().and_then(|mode| string_regex(&mode.value())){
+        Some(regex) => regex,
+        None => return,
+    };
+    for (name, optional) in strings{
+        let regex = if *optional{
+            format!("({})?", regex)
+        }else{
+            regex.to_string()
+        };
+        for field in fields.iter_mut().filter(|f| f.name == *name){
+            field.regex = quote!{ #regex };
+        }
+    }
+}
+
+fn quote_parse_nom(ds: &DeriveInput)->TokenStream{
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let name = &ds.ident;
+    quote!{
+        impl #impl_generics #name #ty_generics #where_clause{
+            /// Parse value from the start of input, returning rest of input.
+            /// Usable as `nom` parser.
+            pub fn parse_nom(input: &str)->::reformation::nom::IResult<&str, Self>{
+                ::reformation::parse_nom(input)
+            }
+        }
+    }
+}
+
+/// `parse_opt` matching static regular expression of implementation, rather than
+/// looking up `regex_str` in `cached_regex` on each call, as default method does
+fn quote_parse_opt(ds: &DeriveInput)->TokenStream{
+    let re = quote_regex(ds);
+    quote!{
+        fn parse_opt(input_str: &str)->Option<Self>{
+            #re
+            let captures = re.captures(input_str)?;
+            <Self as ::reformation::Reformation>::from_captures(&captures, 1).ok()
+        }
+    }
+}
+
+fn quote_is(ds: &DeriveInput)->TokenStream{
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let name = &ds.ident;
+    quote!{
+        impl #impl_generics #name #ty_generics #where_clause{
+            /// Parse string and compare result with expected value.
+            /// Input, which cannot be parsed, is not equal to any value.
+            pub fn is(input_str: &str, expected: &Self)->bool
+                where Self: ::std::cmp::PartialEq
+            {
+                match <Self as ::reformation::Reformation>::parse_opt(input_str){
+                    Some(value) => value == *expected,
+                    None => false,
+                }
+            }
+        }
+    }
+}
+
+fn quote_canonical(ds: &DeriveInput, segments: &[Segment])->Result<TokenStream, String>{
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let name = &ds.ident;
+    let fields = get_fields(ds).map_err(|_| "Only structs with named fields can be rendered.".to_string())?;
+    let parts = segments.iter()
+        .map(|segment| match segment{
+            Segment::Literal(literal) => {
+                let literal = canonical_literal(literal)?;
+                Ok(quote!{ res.push_str(#literal); })
+            },
+            Segment::Field(arg) => {
+                let field = fields.iter()
+                    .filter_map(|f| f.ident.as_ref())
+                    .find(|ident| field_name(ident) == *arg)
+                    .ok_or_else(|| format!("Format string references unknown field `{}`.", arg))?;
+                Ok(quote!{ res.push_str(&self.#field.to_string()); })
+            },
+            Segment::Group(arg) => Err(format!("Field `{}` bound to named group cannot be rendered.", arg)),
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(quote!{
+        impl #impl_generics #name #ty_generics #where_clause{
+            /// Render value in canonical form of its format.
+            pub fn canonical(&self)->String{
+                let mut res = String::new();
+                #(#parts)*
+                res
+            }
+        }
+    })
+}
+
+/// Text matched by literal part of format string in canonical form:
+/// optional whitespace is omitted, and required one is replaced by single space.
+fn canonical_literal(regex: &str)->Result<String, String>{
+    let mut res = String::new();
+    let mut chars = regex.chars().peekable();
+    while let Some(c) = chars.next(){
+        let space = match c{
+            '\\' => match chars.next(){
+                Some('s') => true,
+                Some(c) if !c.is_alphanumeric() => {
+                    res.push(c);
+                    false
+                },
+                _ => return Err(format!("Cannot create canonical form of format part r{:?}.", regex)),
+            },
+            ' ' => true,
+            '^' | '$' => false,
+            '.' | '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' => {
+                return Err(format!("Cannot create canonical form of format part r{:?}.", regex));
+            },
+            c => {
+                res.push(c);
+                false
+            }
+        };
+        if space{
+            match chars.peek(){
+                Some('*') | Some('?') => {
+                    chars.next();
+                },
+                Some('+') => {
+                    chars.next();
+                    res.push(' ');
+                },
+                _ => res.push(' '),
+            }
+        }
+    }
+    Ok(res)
+}
+
+
+/// Format of enum variant `#[reformation(r"format")]`, or `#[reformation(empty)]`
+/// for unit variant matching empty string
+struct VariantAttribute{
+    format: String,
+    empty: bool,
+}
+
+impl Parse for VariantAttribute{
+    fn parse(input: ParseStream)->syn::Result<Self>{
+        let content;
+        parenthesized!(content in input);
+        if content.peek(LitStr){
+            let format: LitStr = content.parse()?;
+            return Ok(Self{format: format.value(), empty: false});
+        }
+        let name: Ident = content.parse()?;
+        if name != "empty"{
+            let msg = format!("Unknown reformation argument `{}`.", name);
+            return Err(syn::Error::new(name.span(), msg));
+        }
+        Ok(Self{format: String::new(), empty: true})
+    }
+}
+
+/// Arguments of enum level attribute `#[reformation(args..)]`
+#[derive(Default)]
+struct EnumAttribute{
+    /// `repr`: parse numeric value of discriminant
+    repr: bool,
+    /// `ignore_case_fields`: literal parts of variant formats are case insensitive
+    ignore_case: bool,
+    /// `sequential`: `FromStr` tries variants in order of declaration, each matching whole input
+    sequential: bool,
+    /// `try_from`: implement `TryFrom<&str>`, `TryFrom<String>` and `TryFrom<&String>`
+    try_from: bool,
+}
+
+impl Parse for EnumAttribute{
+    fn parse(input: ParseStream)->syn::Result<Self>{
+        let content;
+        parenthesized!(content in input);
+        let mut res = Self::default();
+        while !content.is_empty(){
+            let name: Ident = content.parse()?;
+            match name.to_string().as_str(){
+                "repr" => {
+                    res.repr = true;
+                },
+                "ignore_case_fields" => {
+                    res.ignore_case = true;
+                },
+                "sequential" => {
+                    res.sequential = true;
+                },
+                "try_from" => {
+                    res.try_from = true;
+                },
+                other => {
+                    let msg = format!("Unknown reformation argument `{}`.", other);
+                    return Err(syn::Error::new(name.span(), msg));
+                }
+            }
+            if !content.is_empty(){
+                content.parse::<Token![,]>()?;
+            }
+        }
+        Ok(res)
+    }
+}
+
+fn impl_enum_body(ds: &DeriveInput, de: &DataEnum)->Result<TokenStream, TokenStream>{
+    let name = &ds.ident;
+    let attr = match merged_re_parse_attributes(&ds.attrs){
+        Some(tts) => syn::parse2::<EnumAttribute>(tts)
+            .map_err(|e| e.to_compile_error())?,
+        None => EnumAttribute::default(),
+    };
+    if attr.repr{
+        if attr.sequential{
+            return Err(quote_spanned!{ds.span()=>
+                compile_error!{"`repr` cannot be combined with `sequential`."}
+            });
+        }
+        return impl_repr_enum_body(ds, de, attr.try_from);
+    }
+    let mut generics = ds.generics.clone();
+    add_trait_bounds(&mut generics);
+    let ds = &DeriveInput{generics, ..ds.clone()};
+    check_not_recursive(ds, de.variants.iter().flat_map(|v| &v.fields))?;
+    // variant name is used as format of unit variant, if it is not specified explicitly
+    let mut empty_variant: Option<&Ident> = None;
+    let variants = de.variants.iter()
+        .map(|v|{
+            let format = match v.attrs.iter().filter_map(get_re_parse_attribute).next(){
+                Some(tts) => {
+                    let attr = syn::parse2::<VariantAttribute>(tts.clone())
+                        .map_err(|e| e.to_compile_error())?;
+                    if attr.empty{
+                        if let Some(other) = empty_variant{
+                            let msg = format!("Variants `{}` and `{}` are both marked with `#[reformation(empty)]`.", other, v.ident);
+                            return Err(quote_spanned!{v.span()=>
+                                compile_error!{#msg}
+                            });
+                        }
+                        match v.fields{
+                            Fields::Unit => {},
+                            _ => return Err(quote_spanned!{v.span()=>
+                                compile_error!{"Only unit variant can be marked with `#[reformation(empty)]`."}
+                            }),
+                        }
+                        empty_variant = Some(&v.ident);
+                    }
+                    attr.format
+                },
+                None => if let Fields::Unit = v.fields{
+                    escape_regex(&v.ident.to_string())
+                }else{
+                    return Err(quote_spanned!{v.span()=>
+                        compile_error!{"Attribute #[reformation(r\"..\")] containing format string is required for variants with fields."}
+                    });
+                },
+            };
+            Ok((v, format))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let chars: Option<Vec<_>> = variants.iter()
+        .map(|(v, format)| match v.fields{
+            Fields::Unit if !attr.ignore_case && !attr.sequential => literal_char(format),
+            _ => None,
+        })
+        .collect();
+    let (re_parse_body, sequential_body) = match chars{
+        Some(chars) => {
+            let variants: Vec<_> = variants.iter().map(|(v, format)| (&v.ident, format.clone())).collect();
+            if debug_enabled(){
+                for (ident, format) in &variants{
+                    eprintln!("{}", debug_description(&format!("{}::{}", name, ident), format, &[]));
+                }
+            }
+            (quote_enum_from_chars(ds, &variants, &chars)?, None)
+        },
+        None => quote_enum_from_formats(ds, &variants, attr.ignore_case, attr.sequential)?,
+    };
+
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let (from_str_body, parse_opt_body) = match sequential_body{
+        // `parse_opt` tries variants in the same order as `FromStr`
+        Some(body) => (quote_impl_from_str_with(ds, None, body, attr.try_from), quote!{
+            fn parse_opt(input_str: &str)->Option<Self>{
+                <Self as ::std::str::FromStr>::from_str(input_str).ok()
+            }
+        }),
+        None => (quote_impl_from_str(ds, None, attr.try_from), quote_parse_opt(ds)),
+    };
+    Ok(quote!{
+        impl #impl_generics ::reformation::Reformation for #name #ty_generics #where_clause{
+            #re_parse_body
+
+            #parse_opt_body
+        }
+
+        #from_str_body
+    })
+}
+
+/// Enum is parsed from numeric value of its discriminant
+fn impl_repr_enum_body(ds: &DeriveInput, de: &DataEnum, try_from: bool)->Result<TokenStream, TokenStream>{
+    let name = &ds.ident;
+    let repr = repr_type(ds).ok_or_else(|| quote_spanned!{ds.span()=>
+        compile_error!{"`#[reformation(repr)]` requires integer `#[repr(...)]` attribute."}
+    })?;
+    for v in &de.variants{
+        if let Fields::Unit = v.fields{
+        }else{
+            return Err(quote_spanned!{v.span()=>
+                compile_error!{"reformation supports only unit variants of enums."}
+            });
+        }
+    }
+    if debug_enabled(){
+        eprintln!("{}", debug_description(&name.to_string(), &format!("<{}>", quote!(#repr)), &[]));
+    }
+    let idents1 = de.variants.iter().map(|v| &v.ident);
+    let idents2 = idents1.clone();
+    let names1 = std::iter::repeat(name);
+    let names2 = std::iter::repeat(name);
+    let reprs = std::iter::repeat(&repr);
+    let (impl_generics, ty_generics, where_clause) = ds.generics.split_for_impl();
+    let from_str_body = quote_impl_from_str(ds, None, try_from);
+    let parse_opt_body = quote_parse_opt(ds);
+    Ok(quote!{
+        impl #impl_generics ::reformation::Reformation for #name #ty_generics #where_clause{
+            fn regex_str()->&'static str{
+                <#repr as ::reformation::Reformation>::regex_str()
+            }
+
+            #parse_opt_body
+
+            fn captures_count()->usize{
+                <#repr as ::reformation::Reformation>::captures_count()
+            }
+
+            fn from_captures(captures: &::reformation::Captures, offset: usize)->Result<Self, Box<dyn ::std::error::Error>>{
+                let value = <#repr as ::reformation::Reformation>::from_captures(captures, offset)?;
+                #(
+                    if value == #names1::#idents1 as #reprs{
+                        return Ok(#names2::#idents2);
+                    }
+                )*
+                Err(format!("{} is not a discriminant of `{}`", value, stringify!(#name)).into())
+            }
+        }
+
+        #from_str_body
+    })
+}
+
+/// Integer type from `#[repr(...)]` attribute
+fn repr_type(ds: &DeriveInput)->Option<Ident>{
+    let ints = [
+        "u8", "u16", "u32", "u64", "u128", "usize",
+        "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    ds.attrs.iter()
+        .filter_map(|a| a.parse_meta().ok())
+        .filter_map(|meta| match meta{
+            syn::Meta::List(ref list) if list.ident == "repr" => Some(list.nested.clone()),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested{
+            syn::NestedMeta::Meta(syn::Meta::Word(ident)) => Some(ident),
+            _ => None,
+        })
+        .find(|ident| ints.contains(&ident.to_string().as_str()))
+}
+
+/// Every variant is single character: match them with character class
+fn quote_enum_from_chars(ds: &DeriveInput, variants: &[(&Ident, String)], chars: &[char])->Result<TokenStream, TokenStream>{
+    for (i, c) in chars.iter().enumerate(){
+        if let Some(j) = chars[..i].iter().position(|x| x == c){
+            let msg = format!("Variants `{}` and `{}` match the same character {:?}.", variants[j].0, variants[i].0, c);
+            return Err(quote_spanned!{variants[i].0.span()=>
+                compile_error!{#msg}
+            });
+        }
+    }
+    let name = &ds.ident;
+    let regex = format!("([{}])", chars.iter().map(|c| escape_regex(&c.to_string())).collect::<String>());
+    let idents = variants.iter().map(|(ident, _)| ident);
+    let strs = chars.iter().map(|c| c.to_string());
+    let names = std::iter::repeat(name);
+    Ok(quote!{
+        fn regex_str()->&'static str{
+            #regex
+        }
+
+        fn captures_count()->usize{
+            1
+        }
+
+        fn min_len()->usize{
+            1
+        }
+
+        fn from_captures(captures: &::reformation::Captures, offset: usize)->Result<Self, Box<dyn ::std::error::Error>>{
+            let value = captures.get(offset).map(|x| x.as_str()).unwrap_or("");
+            match value{
+                #(#strs => Ok(#names::#idents),)*
+                _ => Err(format!("{:?} does not match any variant of `{}`", value, stringify!(#name)).into()),
+            }
+        }
+    })
+}
+
+/// Variants are alternatives, each placed in its own capture group.
+/// Variants with more fields are tried first, so `{}` does not shadow `{} {}`.
+/// With `sequential` variants keep order of declaration, and body of `from_str` trying
+/// anchored regular expression of each variant in turn is returned as well.
+fn quote_enum_from_formats(ds: &DeriveInput, variants: &[(&Variant, String)], ignore_case: bool, sequential: bool)
+    ->Result<(TokenStream, Option<TokenStream>), TokenStream>
+{
+    let name = &ds.ident;
+    let mut variants: Vec<_> = variants.iter()
+        .map(|(v, format)|{
+            // fields of tuple variant are named `_0`, `_1`, ...
+            let names: Vec<_> = v.fields.iter()
+                .enumerate()
+                .map(|(i, f)| f.ident.clone().unwrap_or_else(|| Ident::new(&format!("_{}", i), f.span())))
+                .collect();
+            let format = match v.fields{
+                // unit variant has no placeholders, so braces are regex repetitions,
+                // while `{{` and `}}` are still accepted as escaped braces
+                Fields::Unit => escape_format(&format.replace("{{", "{").replace("}}", "}")),
+                _ => positional_placeholders(format),
+            };
+            let format = if ignore_case{
+                ignore_literal_case(&format)
+            }else{
+                format
+            };
+            (*v, format, names)
+        })
+        .collect();
+    // variant matching empty string would shadow every other one, so it is tried last
+    if !sequential{
+        variants.sort_by_key(|(v, format, _)| (format.is_empty(), std::cmp::Reverse(v.fields.iter().count())));
+    }
+
+    let mut regexes = vec![];
+    let mut counts = vec![];
+    let mut parses = vec![];
+    let mut tries = vec![];
+    for (v, format, names) in &variants{
+        let ident = &v.ident;
+        let segments = segments(format);
+        let args = arguments(&segments);
+        let fields = v.fields.iter().zip(names)
+            .map(|(field, name)| FieldCode::new(field, name))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(arg) = args.iter().find(|arg| !fields.iter().any(|f| field_name(f.name) == **arg)){
+            let msg = format!("Format string of variant `{}` references unknown field `{}`.", ident, arg);
+            return Err(quote_spanned!{v.span()=>
+                compile_error!{#msg}
+            });
+        }
+        for field in &fields{
+            let occurrences = segments.iter()
+                .filter(|s| match s{
+                    Segment::Field(arg) => field_name(field.name) == *arg,
+                    _ => false,
+                })
+                .count();
+            if occurrences == 0 || (occurrences > 1 && !field.reuse){
+                let msg = format!(
+                    "Field `{}` of variant `{}` must appear in format string exactly once, unless it is marked with `#[reformation(reuse)]`.",
+                    field.name, ident
+                );
+                return Err(quote_spanned!{v.span()=>
+                    compile_error!{#msg}
+                });
+            }
+        }
+        if debug_enabled(){
+            let placeholders: Vec<_> = fields.iter()
+                .map(|f| (format!("{{{}}}", field_name(f.name)), f.ty_name.clone()))
+                .collect();
+            eprintln!("{}", debug_description(&format!("{}::{}", name, ident), &debug_pattern(format, &fields), &placeholders));
+        }
+
+        let names1 = fields.iter().map(|f| f.name);
+        let field_regexes = fields.iter().map(|f| &f.regex);
+        regexes.push(if fields.is_empty(){
+            let literal: String = segments.iter()
+                .filter_map(|s| match s{
+                    Segment::Literal(s) => Some(s.as_str()),
+                    _ => None,
+                })
+                .collect();
+            quote!{ ::reformation::non_capturing(#literal) }
+        }else{
+            quote!{ format!(#format, #(#names1 = #field_regexes),*) }
+        });
+
+        let (field_counts, field_parses) = quote_alternative(&segments, &fields);
+        let field_counts2 = field_counts.clone();
+        counts.push(quote!{ 1 #(+ #field_counts)* });
+        let construct = match v.fields{
+            Fields::Unit => quote!{ #name::#ident },
+            Fields::Named(_) => quote!{ #name::#ident{ #(#names,)* } },
+            Fields::Unnamed(_) => quote!{ #name::#ident( #(#names,)* ) },
+        };
+        let field_parses2 = field_parses.clone();
+        parses.push(quote!{
+            if captures.get(offset).is_some(){
+                let offset = offset + 1;
+                #(#field_parses2)*
+                let _ = offset;
+                return Ok((#construct, end));
+            }
+            let offset = offset + 1 #(+ #field_counts2)*;
+        });
+        if sequential{
+            let regex = regexes.last().unwrap();
+            let re = if is_generic(ds){
+                // each variant has its own regular expression, identified by type of variant index
+                let index = tries.len();
+                quote!{
+                    let re = ::reformation::generic_regex::<(Self, [(); #index]), _>(|| format!(r"\A(?:{})\z", #regex))
+                        .unwrap_or_else(|x| panic!("Cannot compile regex {:?}", x));
+                }
+            }else{
+                quote!{
+                    ::reformation::lazy_static!{
+                        static ref RE: ::reformation::Regex = {
+                            ::reformation::cached_regex(&format!(r"\A(?:{})\z", #regex))
+                                .unwrap_or_else(|x| panic!("Cannot compile regex {:?}", x))
+                        };
+                    }
+                    let re: &::reformation::Regex = &RE;
+                }
+            };
+            tries.push(quote!{{
+                #re
+                if let Some(captures) = re.captures(input_str){
+                    let result = (||->Result<Self, Box<dyn ::std::error::Error>>{
+                        let offset = 1;
+                        #(#field_parses)*
+                        let _ = offset;
+                        Ok(#construct)
+                    })();
+                    match result{
+                        Ok(value) => return Ok(value),
+                        Err(e) => error = Some(e),
+                    }
+                }
+            }});
+        }
+    }
+    let sequential_body = if sequential{
+        Some(quote!{
+            // variant, which matched input but failed to parse, is reported if no other one succeeds
+            let mut error: Option<Box<dyn ::std::error::Error>> = None;
+            #(#tries)*
+            Err(match error{
+                Some(e) => <Self::Err as ::reformation::ParseError>::invalid_value(<Self as ::reformation::Reformation>::regex_str(), input_str, e),
+                None => <Self::Err as ::reformation::ParseError>::no_match(<Self as ::reformation::Reformation>::regex_str(), input_str),
+            })
+        })
+    }else{
+        None
+    };
+
+    let min_len = variants.iter()
+        .map(|(_, format, _)| format_min_len(format))
+        .min()
+        .unwrap_or(0);
+    let regex_str = quote!{{
+        let variants: Vec<String> = vec![#(#regexes),*];
+        let variants: Vec<_> = variants.iter()
+            .map(|variant| format!("({})", variant))
+            .collect();
+        format!("(?:{})", variants.join("|"))
+    }};
+    let regex_str = if is_generic(ds){
+        quote!{
+            ::reformation::generic_regex_str::<Self, _>(|| #regex_str)
+        }
+    }else{
+        quote!{
+            ::reformation::lazy_static!{
+                static ref STR: String = #regex_str;
+            }
+            &STR
+        }
+    };
+    Ok((quote!{
+        fn regex_str()->&'static str{
+            #regex_str
+        }
+
+        fn captures_count()->usize{
+            let mut count = 0;
+            #(count += #counts;)*
+            count
+        }
+
+        fn min_len()->usize{
+            #min_len
+        }
+
+        fn from_captures(captures: &::reformation::Captures, offset: usize)->Result<Self, Box<dyn ::std::error::Error>>{
+            Self::from_captures_consuming(captures, offset).map(|(value, _)| value)
+        }
+
+        fn from_captures_consuming(captures: &::reformation::Captures, offset: usize)
+            ->Result<(Self, usize), Box<dyn ::std::error::Error>>
+        {
+            let end = offset + <Self as ::reformation::Reformation>::captures_count();
+            #(#parses)*
+            let _ = offset;
+            Err(format!("Input does not match any variant of `{}`", stringify!(#name)).into())
+        }
+    }, sequential_body))
+}
+
+/// Make literal parts of format string case insensitive, keeping field regular expressions intact
+fn ignore_literal_case(format: &str)->String{
+    segments(format).iter()
+        .map(|segment| match segment{
+            Segment::Literal(literal) => escape_format(&format!("(?i:{})", literal)),
+            Segment::Field(arg) | Segment::Group(arg) => format!("{{{}}}", arg),
+        })
+        .collect()
+}
+
+/// Replace positional placeholders `{}` and `{0}` with names of tuple fields `{_0}`
+fn positional_placeholders(format: &str)->String{
+    let mut res = String::new();
+    let mut next = 0;
+    let mut iter = format.chars().peekable();
+    while let Some(c) = iter.next(){
+        match c{
+            '{' if iter.peek() == Some(&'{') => {
+                iter.next();
+                res.push_str("{{");
+            },
+            '{' => {
+                let arg: String = iter.by_ref().take_while(|c| *c != '}').collect();
+                if arg.is_empty(){
+                    res.push_str(&format!("{{_{}}}", next));
+                    next += 1;
+                }else if arg.chars().all(|c| c.is_ascii_digit()){
+                    res.push_str(&format!("{{_{}}}", arg));
+                }else{
+                    res.push_str(&format!("{{{}}}", arg));
+                }
+            },
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Character matched by regular expression, if it is single (possibly escaped) character
+fn literal_char(format: &str)->Option<char>{
+    let meta = r"\.+*?()|[]{}^$";
+    let escapable = r"\.+*?()|[]{}^$#&-~";
+    let mut chars = format.chars();
+    match (chars.next(), chars.next(), chars.next()){
+        (Some(c), None, None) if !meta.contains(c) => Some(c),
+        (Some('\\'), Some(c), None) if escapable.contains(c) => Some(c),
+        _ => None,
+    }
+}
+
+/// Lower bound of length of string matched by format string, counting only its
+/// literal characters: placeholders and optional parts match at least nothing.
+fn format_min_len(format: &str)->usize{
+    let re: String = segments(format).iter()
+        .map(|s| match s{
+            Segment::Literal(s) => s.as_str(),
+            // empty group, so quantifier after placeholder stays valid
+            Segment::Field(_) | Segment::Group(_) => "()",
+        })
+        .collect();
+    regex_min_len(&mut re.chars().peekable())
+}
+
+type RegexChars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+/// Minimal length of string matched by alternation, up to `)` closing current group
+fn regex_min_len(chars: &mut RegexChars)->usize{
+    let mut best: Option<usize> = None;
+    let mut current = 0;
+    while let Some(&c) = chars.peek(){
+        match c{
+            ')' => break,
+            '|' => {
+                chars.next();
+                best = Some(best.map_or(current, |b| b.min(current)));
+                current = 0;
+            },
+            _ => {
+                let atom = regex_atom_min_len(chars);
+                current += atom * regex_quantifier_min(chars);
+            },
+        }
+    }
+    best.map_or(current, |b| b.min(current))
+}
+
+fn regex_atom_min_len(chars: &mut RegexChars)->usize{
+    match chars.next(){
+        Some('\\') => match chars.next(){
+            Some('b') | Some('B') | Some('A') | Some('z') => 0,
+            Some(c @ 'p') | Some(c @ 'P') | Some(c @ 'x') | Some(c @ 'u') | Some(c @ 'U') => {
+                if chars.peek() == Some(&'{'){
+                    chars.by_ref().take_while(|c| *c != '}').for_each(drop);
+                }else{
+                    let digits = match c{
+                        'p' | 'P' => 1,
+                        'x' => 2,
+                        'u' => 4,
+                        _ => 8,
+                    };
+                    chars.by_ref().take(digits).for_each(drop);
+                }
+                1
+            },
+            _ => 1,
+        },
+        Some('[') => {
+            // `]` right after opening bracket (or negation) is literal
+            if chars.peek() == Some(&'^'){
+                chars.next();
+            }
+            if chars.peek() == Some(&']'){
+                chars.next();
+            }
+            let mut depth = 1;
+            while let Some(c) = chars.next(){
+                match c{
+                    '\\' => {
+                        chars.next();
+                    },
+                    '[' => depth += 1,
+                    ']' => {
+                        depth -= 1;
+                        if depth == 0{
+                            break;
+                        }
+                    },
+                    _ => {},
+                }
+            }
+            1
+        },
+        Some('(') => {
+            if chars.peek() == Some(&'?'){
+                chars.next();
+                match chars.peek(){
+                    Some('P') | Some('<') => {
+                        chars.by_ref().take_while(|c| *c != '>').for_each(drop);
+                    },
+                    _ => {
+                        // flags, either for the rest of group `(?i)` or for its own group `(?i:...)`
+                        for c in chars.by_ref(){
+                            match c{
+                                ':' => break,
+                                ')' => return 0,
+                                _ => {},
+                            }
+                        }
+                    },
+                }
+            }
+            let len = regex_min_len(chars);
+            chars.next();
+            len
+        },
+        Some('^') | Some('$') | None => 0,
+        Some(_) => 1,
+    }
+}
+
+/// Minimal number of repetitions, consuming quantifier following an atom
+fn regex_quantifier_min(chars: &mut RegexChars)->usize{
+    let min = match chars.peek(){
+        Some('?') | Some('*') => 0,
+        Some('+') => 1,
+        Some('{') => {
+            chars.next();
+            let repetition: String = chars.by_ref().take_while(|c| *c != '}').collect();
+            let min = repetition.split(',').next().unwrap_or("").trim();
+            // lazy modifier
+            if chars.peek() == Some(&'?'){
+                chars.next();
+            }
+            return min.parse().unwrap_or(1);
+        },
+        _ => return 1,
+    };
+    chars.next();
+    if chars.peek() == Some(&'?'){
+        chars.next();
+    }
+    min
+}
+
+
+/// Bounds of `range = "a..=b"` field attribute
+struct FloatRange{
+    text: String,
+    start: Option<f64>,
+    end: Option<f64>,
+    inclusive: bool,
+}
+
+impl FloatRange{
+    fn parse(text: &str)->Option<Self>{
+        let (start, end, inclusive) = if let Some(i) = text.find("..="){
+            (&text[..i], &text[i + 3..], true)
+        }else{
+            let i = text.find("..")?;
+            (&text[..i], &text[i + 2..], false)
+        };
+        let bound = |s: &str|->Option<Option<f64>>{
+            let s = s.trim();
+            if s.is_empty(){
+                Some(None)
+            }else{
+                s.parse().ok().map(Some)
+            }
+        };
+        let (start, end) = (bound(start)?, bound(end)?);
+        if inclusive && end.is_none(){
+            return None;
+        }
+        Some(Self{text: text.to_string(), start, end, inclusive})
+    }
+
+    /// Expression checking that `value` is within range, with bounds converted
+    /// to expressions of value type by `bound`
+    fn quote_contains(&self, bound: impl Fn(f64)->TokenStream)->TokenStream{
+        let start = self.start.map(&bound);
+        let end = self.end.map(&bound);
+        let range = if self.inclusive{
+            quote!{ #start..=#end }
+        }else{
+            quote!{ #start..#end }
+        };
+        quote!{ ::std::ops::RangeBounds::contains(&(#range), &value) }
+    }
+}
+
+/// Signs of number, same as in character class `reformation::SIGN`
+const SIGNS: &str = r"\+-";
+
+/// Character class of sign of number, with unicode minus `−` (U+2212) if `unicode_minus` is set
+fn sign_class(unicode_minus: bool)->String{
+    if unicode_minus{
+        format!("[\u{2212}{}]", SIGNS)
+    }else{
+        format!("[{}]", SIGNS)
+    }
+}
+
+/// Attributes transforming captured string before parsing, for error messages
+const NUMBER_FORMAT_ATTRIBUTES: &str =
+    "`radix`, `accounting`, `unicode_minus`, `decimal`, `grouping`, `strict_float`, `scale`, `clamp`, `auto_decimal` or `allow_leading_zeros`";
+
+/// Arguments of field level attribute `#[reformation(args..)]`
+#[derive(Default)]
+struct FieldAttribute{
+    /// `radix = N`: parse integer in given radix
+    radix: Option<u32>,
+    /// `twos_complement`: with `radix`, parse digits as two's complement bits
+    twos_complement: bool,
+    /// `accounting`: negative values are written in parentheses: `(42)`
+    accounting: bool,
+    /// `unicode_minus`: accept unicode minus sign `−` (U+2212) along with `-`
+    unicode_minus: bool,
+    /// `default_if_absent`: use `Default::default()` if field is inside
+    /// of optional group, which did not participate in match
+    default_if_absent: bool,
+    /// `default = "expr"`: value of field, which does not appear in format string
+    /// or is inside of optional group, which did not participate in match
+    default: Option<syn::Expr>,
+    /// `separator = ","`: field is collection of items separated by literal string
+    separator: Option<LitStr>,
+    /// `trailing_separator = "forbid" | "allow" | "require"`
+    trailing_separator: Option<Ident>,
+    /// `duplicates = "dedupe" | "error"`: policy for duplicate items of set
+    duplicates: Option<Ident>,
+    /// `decimal = ","`: decimal separator of floating point number
+    decimal: Option<LitStr>,
+    /// `grouping = ","`: thousands separator of number
+    grouping: Option<LitStr>,
+    /// `auto_decimal`: either `.` or `,` is decimal separator, but not both
+    auto_decimal: bool,
+    /// `strict_float`: decimal point must have digits on both sides
+    strict_float: bool,
+    /// `scale = N`: integer stores decimal number multiplied by `10^N`
+    scale: Option<u32>,
+    /// `allow_leading_zeros`: strip leading zeros of numbers before parsing
+    allow_leading_zeros: bool,
+    /// `max_len = N`: string field matches at most `N` characters
+    max_len: Option<u64>,
+    /// `flag = "--verbose"`: boolean field, which is true if literal is present
+    flag: Option<LitStr>,
+    /// `count = 'a'`: `usize` field is number of repetitions of character
+    count: Option<LitChar>,
+    /// `count_of = "X"`: `usize` field is number of matches of regular expression
+    count_of: Option<LitStr>,
+    /// `validate = path::to_predicate`: check parsed value
+    validate: Option<syn::Path>,
+    /// `range = "0.0..=1.0"`: parsed number must be within range
+    range: Option<FloatRange>,
+    /// `digits = "19..=39"`: number of decimal digits must be within range
+    digits: Option<FloatRange>,
+    /// `or = "expr"`: value of `Option` field if its group did not participate in match
+    or: Option<syn::Expr>,
+    /// `when = "expr"`: field is present only if condition on previous fields holds
+    when: Option<LitStr>,
+    /// `checked`: report integer overflow naming the field
+    checked: bool,
+    /// `clamp`: integer overflow gives minimal or maximal value of type
+    clamp: bool,
+    /// `capture = "whole"`: field receives whole match of regular expression
+    capture_whole: bool,
+    /// `reuse`: field may appear in format string multiple times,
+    /// all occurrences must have equal values
+    reuse: bool,
+}
+
+impl FieldAttribute{
+    fn from_field(field: &Field)->syn::Result<Self>{
+        match merged_re_parse_attributes(&field.attrs){
+            Some(tts) => syn::parse2(tts),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Is captured string transformed before parsing
+    fn transforms_str(&self)->bool{
+        self.radix.is_some() || self.accounting || self.unicode_minus || self.decimal.is_some() || self.strict_float
+            || self.allow_leading_zeros || self.grouping.is_some() || self.scale.is_some() || self.clamp
+            || self.auto_decimal
+    }
+}
+
+impl Parse for FieldAttribute{
+    fn parse(input: ParseStream)->syn::Result<Self>{
+        let content;
+        parenthesized!(content in input);
+        let mut res = Self::default();
+        let mut names: Vec<Ident> = vec![];
+        while !content.is_empty(){
+            let name: Ident = content.parse()?;
+            names.push(name.clone());
+            match name.to_string().as_str(){
+                "radix" => {
+                    content.parse::<Token![=]>()?;
+                    let radix: LitInt = content.parse()?;
+                    if radix.value() < 2 || radix.value() > 36{
+                        return Err(syn::Error::new(radix.span(), "Radix must be in range 2..=36."));
+                    }
+                    res.radix = Some(radix.value() as u32);
+                },
+                "twos_complement" => {
+                    res.twos_complement = true;
+                },
+                "accounting" => {
+                    res.accounting = true;
+                },
+                "unicode_minus" => {
+                    res.unicode_minus = true;
+                },
+                "default_if_absent" => {
+                    res.default_if_absent = true;
+                },
+                "default" => {
+                    content.parse::<Token![=]>()?;
+                    let expr: LitStr = content.parse()?;
+                    res.default = Some(default_expr(expr.parse()?));
+                },
+                "separator" => {
+                    content.parse::<Token![=]>()?;
+                    res.separator = Some(content.parse()?);
+                },
+                "trailing_separator" => {
+                    content.parse::<Token![=]>()?;
+                    let policy: LitStr = content.parse()?;
+                    let variant = match policy.value().as_str(){
+                        "forbid" => "Forbid",
+                        "allow" => "Allow",
+                        "require" => "Require",
+                        _ => {
+                            let msg = "Trailing separator policy must be one of \"forbid\", \"allow\" or \"require\".";
+                            return Err(syn::Error::new(policy.span(), msg));
+                        }
+                    };
+                    res.trailing_separator = Some(Ident::new(variant, policy.span()));
+                },
+                "duplicates" => {
+                    content.parse::<Token![=]>()?;
+                    let policy: LitStr = content.parse()?;
+                    let variant = match policy.value().as_str(){
+                        "dedupe" => "Dedupe",
+                        "error" => "Error",
+                        _ => {
+                            let msg = "Duplicates policy must be one of \"dedupe\" or \"error\".";
+                            return Err(syn::Error::new(policy.span(), msg));
+                        }
+                    };
+                    res.duplicates = Some(Ident::new(variant, policy.span()));
+                },
+                "strict_float" => {
+                    res.strict_float = true;
+                },
+                "scale" => {
+                    content.parse::<Token![=]>()?;
+                    let scale: LitInt = content.parse()?;
+                    if scale.value() > 38{
+                        return Err(syn::Error::new(scale.span(), "Scale must be in range 0..=38."));
+                    }
+                    res.scale = Some(scale.value() as u32);
+                },
+                "allow_leading_zeros" => {
+                    res.allow_leading_zeros = true;
+                },
+                "decimal" => {
+                    content.parse::<Token![=]>()?;
+                    let decimal: LitStr = content.parse()?;
+                    if decimal.value().chars().count() != 1{
+                        return Err(syn::Error::new(decimal.span(), "Decimal separator must be single character."));
+                    }
+                    res.decimal = Some(decimal);
+                },
+                "grouping" => {
+                    content.parse::<Token![=]>()?;
+                    let grouping: LitStr = content.parse()?;
+                    if grouping.value().chars().count() != 1{
+                        return Err(syn::Error::new(grouping.span(), "Grouping separator must be single character."));
+                    }
+                    res.grouping = Some(grouping);
+                },
+                "max_len" => {
+                    content.parse::<Token![=]>()?;
+                    let max_len: LitInt = content.parse()?;
+                    res.max_len = Some(max_len.value());
+                },
+                "flag" => {
+                    content.parse::<Token![=]>()?;
+                    res.flag = Some(content.parse()?);
+                },
+                "count" => {
+                    content.parse::<Token![=]>()?;
+                    res.count = Some(content.parse()?);
+                },
+                "count_of" => {
+                    content.parse::<Token![=]>()?;
+                    res.count_of = Some(content.parse()?);
+                },
+                "or" => {
+                    content.parse::<Token![=]>()?;
+                    let expr: LitStr = content.parse()?;
+                    res.or = Some(expr.parse()?);
+                },
+                "when" => {
+                    content.parse::<Token![=]>()?;
+                    let condition: LitStr = content.parse()?;
+                    condition.parse::<syn::Expr>()?;
+                    res.when = Some(condition);
+                },
+                "checked" => {
+                    res.checked = true;
+                },
+                "clamp" => {
+                    res.clamp = true;
+                },
+                "auto_decimal" => {
+                    res.auto_decimal = true;
+                },
+                "capture" => {
+                    content.parse::<Token![=]>()?;
+                    let capture: LitStr = content.parse()?;
+                    if capture.value() != "whole"{
+                        return Err(syn::Error::new(capture.span(), "Capture must be \"whole\"."));
+                    }
+                    res.capture_whole = true;
+                },
+                "reuse" => {
+                    res.reuse = true;
+                },
+                "validate" => {
+                    content.parse::<Token![=]>()?;
+                    res.validate = Some(content.parse()?);
+                },
+                "digits" => {
+                    content.parse::<Token![=]>()?;
+                    let digits: LitStr = content.parse()?;
+                    let is_count = |x: Option<f64>| x.is_none_or(|x| x >= 0.0 && x.fract() == 0.0);
+                    res.digits = Some(FloatRange::parse(&digits.value())
+                        .filter(|range| is_count(range.start) && is_count(range.end))
+                        .ok_or_else(|| syn::Error::new(digits.span(), "Digits must be written as `a..b`, `a..=b`, `a..` or `..b`, where bounds are non negative integers."))?);
+                },
+                "range" => {
+                    content.parse::<Token![=]>()?;
+                    let range: LitStr = content.parse()?;
+                    res.range = Some(FloatRange::parse(&range.value())
+                        .ok_or_else(|| syn::Error::new(range.span(), "Range must be written as `a..b`, `a..=b`, `a..` or `..b`, where bounds are numbers."))?);
+                },
+                other => {
+                    let msg = format!("Unknown reformation argument `{}`.", other);
+                    return Err(syn::Error::new(name.span(), msg));
+                }
+            }
+            if !content.is_empty(){
+                content.parse::<Token![,]>()?;
+            }
+        }
+        // conflicting argument is pointed at, rather than the end of attribute
+        let error = |arg: &str, msg: &str|{
+            let span = names.iter().find(|name| *name == arg).map_or_else(proc_macro2::Span::call_site, |name| name.span());
+            syn::Error::new(span, msg)
+        };
+        if res.separator.is_none() && (res.trailing_separator.is_some() || res.duplicates.is_some()){
+            let arg = if res.trailing_separator.is_some(){ "trailing_separator" }else{ "duplicates" };
+            return Err(error(arg, "`trailing_separator` and `duplicates` require `separator`."));
+        }
+        if res.separator.is_some() && res.transforms_str(){
+            return Err(error("separator", &format!("`separator` cannot be combined with {}.", NUMBER_FORMAT_ATTRIBUTES)));
+        }
+        if res.flag.is_some() && (res.separator.is_some() || res.transforms_str()){
+            return Err(error("flag", &format!("`flag` cannot be combined with `separator`, {}.", NUMBER_FORMAT_ATTRIBUTES)));
+        }
+        if res.max_len.is_some() && (res.flag.is_some() || res.separator.is_some() || res.transforms_str()){
+            return Err(error("max_len", &format!("`max_len` cannot be combined with `flag`, `separator`, {}.", NUMBER_FORMAT_ATTRIBUTES)));
+        }
+        if res.count.is_some() && (res.flag.is_some() || res.separator.is_some() || res.max_len.is_some() || res.transforms_str()){
+            return Err(error("count", &format!("`count` cannot be combined with `flag`, `separator`, `max_len`, {}.", NUMBER_FORMAT_ATTRIBUTES)));
+        }
+        if res.count_of.is_some() && (res.count.is_some() || res.flag.is_some() || res.separator.is_some() || res.max_len.is_some() || res.transforms_str()){
+            return Err(error("count_of", &format!("`count_of` cannot be combined with `count`, `flag`, `separator`, `max_len`, {}.", NUMBER_FORMAT_ATTRIBUTES)));
+        }
+        if res.when.is_some() && (res.default_if_absent || res.or.is_some()){
+            return Err(error("when", "`when` cannot be combined with `default_if_absent` or `or`."));
+        }
+        if res.default.is_some() && (res.default_if_absent || res.or.is_some() || res.when.is_some() || res.capture_whole){
+            return Err(error("default", "`default` cannot be combined with `default_if_absent`, `or`, `when` or `capture`."));
+        }
+        if res.digits.is_some() && res.radix.is_some(){
+            return Err(error("digits", "`digits` cannot be combined with `radix`."));
+        }
+        if res.twos_complement && res.radix.is_none(){
+            return Err(error("twos_complement", "`twos_complement` requires `radix`."));
+        }
+        if res.twos_complement && (res.accounting || res.clamp){
+            return Err(error("twos_complement", "`twos_complement` cannot be combined with `accounting` or `clamp`: digits are bits of value, and have neither sign nor overflow."));
+        }
+        if res.auto_decimal && (res.decimal.is_some() || res.grouping.is_some() || res.radix.is_some() || res.scale.is_some() || res.strict_float){
+            return Err(error("auto_decimal", "`auto_decimal` cannot be combined with `decimal`, `grouping`, `radix`, `scale` or `strict_float`."));
+        }
+        if res.clamp && res.checked{
+            return Err(error("clamp", "`clamp` cannot be combined with `checked`: overflow is either clamped or reported."));
+        }
+        if res.radix.is_some() && (res.decimal.is_some() || res.strict_float || res.grouping.is_some()){
+            return Err(error("radix", "`radix` cannot be combined with `decimal`, `strict_float` or `grouping`."));
+        }
+        if res.scale.is_some() && (res.radix.is_some() || res.strict_float){
+            return Err(error("scale", "`scale` cannot be combined with `radix` or `strict_float`."));
+        }
+        if let (Some(grouping), Some(decimal)) = (&res.grouping, &res.decimal){
+            if grouping.value() == decimal.value(){
+                return Err(syn::Error::new(grouping.span(), "Grouping and decimal separators must differ."));
+            }
+        }
+        Ok(res)
+    }
+}
+
+
+/// Expressions for regular expression, captures count and value of single field.
+/// `parse` evaluates to tuple of value and offset of next field.
+#[derive(Clone)]
+struct FieldCode<'a>{
+    name: &'a Ident,
+    ty: &'a Type,
+    /// bound required by field type to be parsed
+    bound: Option<TokenStream>,
+    /// field may appear in format string multiple times
+    reuse: bool,
+    /// field is parsed from named group written by user, rather than placeholder
+    named_group: bool,
+    /// value is matched by placeholder `{_}`, but not stored
+    discarded: bool,
+    ty_name: String,
+    regex: TokenStream,
+    count: TokenStream,
+    parse: TokenStream,
+}
+
+impl<'a> FieldCode<'a>{
+    fn new(field: &'a Field, name: &'a Ident)->Result<Self, TokenStream>{
+        let ty = &field.ty;
+        let attr = FieldAttribute::from_field(field)
+            .map_err(|e| e.to_compile_error())?;
+
+        let mut code = if let Some(flag) = &attr.flag{
+            Self::flag(name, ty, flag)
+        }else if let Some(c) = &attr.count{
+            Self::repetitions(name, ty, c)
+        }else if let Some(pattern) = &attr.count_of{
+            Self::occurrences(name, ty, pattern)
+        }else if let Some(separator) = &attr.separator{
+            Self::collection(name, ty, separator, &attr)
+        }else if attr.transforms_str(){
+            Self::transformed(name, ty, &attr)
+        }else if let Some(max_len) = attr.max_len{
+            let regex = format!("(.{{0,{}}})", max_len);
+            let regex = if is_string(ty){
+                regex
+            }else if option_inner(ty).is_some_and(is_string){
+                format!("({})?", regex)
+            }else{
+                return Err(quote_spanned!{ty.span()=>
+                    compile_error!{"`max_len` requires field of type `String` or `Option<String>`."}
+                });
+            };
+            let mut code = Self::plain(name, ty);
+            code.regex = quote!{ #regex };
+            code
+        }else if attr.range.is_some(){
+            // `range` replaces default bounds of type
+            let mut code = Self::plain(name, ty);
+            code.parse = quote!{{
+                let value = <#ty as ::reformation::Reformation>::from_captures_unbounded(&captures, offset)?;
+                (value, offset + <#ty as ::reformation::Reformation>::captures_count())
+            }};
+            code
+        }else{
+            Self::plain(name, ty)
+        };
+
+        if let Some(digits) = &attr.digits{
+            let parse = &code.parse;
+            let contains = digits.quote_contains(|bound|{
+                let bound = proc_macro2::Literal::usize_suffixed(bound as usize);
+                quote!{ #bound }
+            });
+            let msg = format!("Field `{}` value {{}} has {{}} digits, expected {}", name, digits.text);
+            code.parse = quote!{{
+                if let Some(matched) = captures.get(offset){
+                    let value = matched.as_str().chars().filter(char::is_ascii_digit).count();
+                    if !(#contains){
+                        return Err(format!(#msg, matched.as_str(), value).into());
+                    }
+                }
+                #parse
+            }};
+        }
+        if attr.checked{
+            let parse = &code.parse;
+            let ty_name = &code.ty_name;
+            code.parse = quote!{{
+                #[allow(clippy::needless_question_mark)]
+                let result = (||->Result<_, Box<dyn ::std::error::Error>>{ Ok(#parse) })();
+                match result{
+                    Ok(ok) => ok,
+                    Err(e) => {
+                        let overflow = e.downcast_ref::<::std::num::ParseIntError>()
+                            .map(|e| match e.kind(){
+                                ::std::num::IntErrorKind::PosOverflow | ::std::num::IntErrorKind::NegOverflow => true,
+                                _ => false,
+                            })
+                            .unwrap_or(false);
+                        if overflow{
+                            let value = captures.get(offset).map(|x| x.as_str()).unwrap_or("");
+                            return Err(format!(
+                                "Field `{}`: value {} overflows type `{}`", stringify!(#name), value, #ty_name
+                            ).into());
+                        }
+                        return Err(e);
+                    }
+                }
+            }};
+        }
+        if let Some(condition) = &attr.when{
+            let count = &code.count;
+            let parse = &code.parse;
+            let expr: syn::Expr = condition.parse().map_err(|e| e.to_compile_error())?;
+            let required = format!("Field `{}` is required when `{}`", name, condition.value());
+            let forbidden = format!("Field `{}` is not allowed unless `{}`", name, condition.value());
+            code.parse = quote!{{
+                let count = #count;
+                let present = (offset..offset + count).any(|i| captures.get(i).is_some());
+                if #expr{
+                    if !present{
+                        return Err(#required.into());
+                    }
+                    #parse
+                }else{
+                    if present{
+                        return Err(#forbidden.into());
+                    }
+                    (::std::default::Default::default(), offset + count)
+                }
+            }};
+        }
+        if attr.default_if_absent{
+            let count = &code.count;
+            let parse = &code.parse;
+            code.parse = quote!{{
+                let count = #count;
+                if (offset..offset + count).any(|i| captures.get(i).is_some()){
+                    #parse
+                }else{
+                    (::std::default::Default::default(), offset + count)
+                }
+            }};
+        }
+        if let Some(expr) = &attr.default{
+            let count = &code.count;
+            let parse = &code.parse;
+            code.parse = quote!{{
+                let count = #count;
+                if (offset..offset + count).any(|i| captures.get(i).is_some()){
+                    #parse
+                }else{
+                    (#expr, offset + count)
+                }
+            }};
+        }
+        if let Some(expr) = &attr.or{
+            let parse = &code.parse;
+            code.parse = quote!{{
+                let (value, offset) = #parse;
+                (value.or_else(|| Some(#expr)), offset)
+            }};
+        }
+        code.reuse = attr.reuse;
+        if let Some(range) = &attr.range{
+            let parse = &code.parse;
+            let msg = format!("Field `{}` value {{}} is out of range {}", name, range.text);
+            // bounds are only converted to float types, integer field is rejected by trait bound
+            let contains = range.quote_contains(|bound|{
+                let bound = proc_macro2::Literal::f64_suffixed(bound);
+                quote_spanned!{ty.span()=> <#ty as ::reformation::RangeBound>::range_bound(#bound) }
+            });
+            code.parse = quote!{{
+                let (value, offset) = #parse;
+                if !(#contains){
+                    return Err(format!(#msg, value).into());
+                }
+                (value, offset)
+            }};
+        }
+        if let Some(predicate) = &attr.validate{
+            let parse = &code.parse;
+            let msg = format!("Field `{}` failed validation `{}`: {{}}", name, quote!(#predicate).to_string().replace(' ', ""));
+            code.parse = quote!{{
+                let (value, offset) = #parse;
+                ::reformation::Validation::into_result(#predicate(&value))
+                    .map_err(|e| format!(#msg, e))?;
+                (value, offset)
+            }};
+        }
+        Ok(code)
+    }
+
+    /// Parse field from single capture group, written by user. Type of field
+    /// must match single capture group, which is checked at runtime.
+    fn bind_to_group(&mut self){
+        let parse = &self.parse;
+        let count = &self.count;
+        let msg = format!(
+            "Field `{}` bound to named group must have type matching single capture group, but `{}` matches {{}}",
+            field_name(self.name), self.ty_name
+        );
+        self.named_group = true;
+        self.parse = quote!{{
+            let count = #count;
+            if count != 1{
+                return Err(format!(#msg, count).into());
+            }
+            let (value, _) = #parse;
+            (value, offset + 1)
+        }};
+        self.count = quote!{ 1 };
+    }
+
+    /// Field parsed by `Reformation` implementation of its type
+    fn plain(name: &'a Ident, ty: &'a Type)->Self{
+        Self{
+            name,
+            ty,
+            bound: Some(quote!{ #ty: ::reformation::Reformation }),
+            reuse: false,
+            named_group: false,
+            discarded: false,
+            ty_name: type_name(ty),
+            regex: quote!{ <#ty as ::reformation::Reformation>::regex_str() },
+            count: quote!{ <#ty as ::reformation::Reformation>::captures_count() },
+            parse: quote!{ <#ty as ::reformation::Reformation>::from_captures_consuming(&captures, offset)? },
+        }
+    }
+
+    /// String slice borrowed from input, `None` for optional one if group did not participate
+    fn borrowed(name: &'a Ident, ty: &'a Type, optional: bool)->Self{
+        let value = if optional{
+            quote!{ captures.get(offset).map(|x| x.as_str()) }
+        }else{
+            quote!{ captures.get(offset).map(|x| x.as_str()).unwrap_or("") }
+        };
+        Self{
+            name,
+            ty,
+            bound: None,
+            reuse: false,
+            named_group: false,
+            discarded: false,
+            ty_name: type_name(ty),
+            regex: quote!{ <String as ::reformation::Reformation>::regex_str() },
+            count: quote!{ 1 },
+            parse: quote!{ (#value, offset + 1) },
+        }
+    }
+
+    /// Boolean, which is true if literal is present
+    fn flag(name: &'a Ident, ty: &'a Type, flag: &LitStr)->Self{
+        let regex = format!("({})?", escape_regex(&flag.value()));
+        Self{
+            name,
+            ty,
+            bound: None,
+            reuse: false,
+            named_group: false,
+            discarded: false,
+            ty_name: type_name(ty),
+            regex: quote!{ #regex },
+            count: quote!{ 1 },
+            parse: quote!{ (captures.get(offset).is_some(), offset + 1) },
+        }
+    }
+
+    /// Number of repetitions of character
+    fn repetitions(name: &'a Ident, ty: &'a Type, c: &LitChar)->Self{
+        let regex = format!("((?:{})*)", escape_regex(&c.value().to_string()));
+        Self{
+            name,
+            ty,
+            bound: None,
+            reuse: false,
+            named_group: false,
+            discarded: false,
+            ty_name: type_name(ty),
+            regex: quote!{ #regex },
+            count: quote!{ 1 },
+            parse: quote!{ (captures.get(offset).map(|x| x.as_str().chars().count()).unwrap_or(0), offset + 1) },
+        }
+    }
+
+    /// Number of non-overlapping matches of regular expression within text matched by field
+    fn occurrences(name: &'a Ident, ty: &'a Type, pattern: &LitStr)->Self{
+        let msg = format!("Cannot compile regex of `count_of` of field `{}`: {{:?}}", field_name(name));
+        Self{
+            name,
+            ty,
+            bound: None,
+            reuse: false,
+            named_group: false,
+            discarded: false,
+            ty_name: type_name(ty),
+            regex: quote!{ "(.*)" },
+            count: quote!{ 1 },
+            parse: quote!{{
+                ::reformation::lazy_static!{
+                    static ref COUNT_OF: ::reformation::Regex = {
+                        ::reformation::cached_regex(#pattern)
+                            .unwrap_or_else(|x| panic!(#msg, x))
+                    };
+                }
+                (captures.get(offset).map(|x| COUNT_OF.find_iter(x.as_str()).count()).unwrap_or(0), offset + 1)
+            }},
+        }
+    }
+
+    /// Collection of items separated by literal string
+    fn collection(name: &'a Ident, ty: &'a Type, separator: &LitStr, attr: &FieldAttribute)->Self{
+        let trailing = attr.trailing_separator.as_ref().map(|policy| quote!{
+            .trailing_separator(::reformation::TrailingSeparator::#policy)
+        });
+        let duplicates = attr.duplicates.as_ref().map(|policy| quote!{
+            .duplicates(::reformation::Duplicates::#policy)
+        });
+        let format = quote!{ ::reformation::ListFormat::new(#separator)#trailing #duplicates };
+        // policy for duplicates is meaningful only for sets, error points at the policy
+        let require_set = attr.duplicates.as_ref().map(|policy| quote_spanned!{policy.span()=>
+            fn require_set<C: ::reformation::Set>(){}
+            require_set::<#ty>();
+        });
+        Self{
+            name,
+            ty,
+            bound: Some(quote!{ #ty: ::reformation::Collection }),
+            reuse: false,
+            named_group: false,
+            discarded: false,
+            ty_name: type_name(ty),
+            regex: quote!{ #format.regex_str::<#ty>() },
+            count: quote!{ 1 },
+            parse: quote!{{
+                #require_set
+                let value = captures.get(offset).map(|x| x.as_str()).unwrap_or("");
+                (#format.parse::<#ty>(value)?, offset + 1)
+            }},
+        }
+    }
+
+    /// Value is parsed from single capture group, containing string
+    /// which is transformed before parsing.
+    fn transformed(name: &'a Ident, ty: &'a Type, attr: &FieldAttribute)->Self{
+        let point = attr.decimal.as_ref()
+            .map(|decimal| escape_regex(&decimal.value()))
+            .unwrap_or_else(|| r"\.".to_string());
+        let sign = sign_class(attr.unicode_minus);
+        let mut regex = match attr.radix{
+            Some(radix) => {
+                let regex = format!(r"{}?{}{}", sign, radix_prefix_regex(radix), radix_digits_regex(radix));
+                quote!{ #regex.to_string() }
+            },
+            None if attr.grouping.is_some() => {
+                let grouping = escape_regex(&attr.grouping.as_ref().unwrap().value());
+                let fraction = if attr.strict_float{
+                    format!(r"(?:{}\d+)?(?:[eE]{}?\d+)?", point, sign)
+                }else if attr.scale.is_some(){
+                    format!(r"(?:{}\d*)?", point)
+                }else if attr.decimal.is_some(){
+                    format!(r"(?:{}\d*)?(?:[eE]{}?\d+)?", point, sign)
+                }else{
+                    String::new()
+                };
+                let regex = format!(r"{}?(?:\d{{1,3}}(?:{}\d{{3}})+|\d+){}", sign, grouping, fraction);
+                quote!{ #regex.to_string() }
+            },
+            None if attr.scale.is_some() => {
+                let regex = format!(r"{1}?(?:\d+(?:{0}\d*)?|{0}\d+)", point, sign);
+                quote!{ #regex.to_string() }
+            },
+            None if attr.strict_float => {
+                let regex = format!(r"{1}?\d+(?:{0}\d+)?(?:[eE]{1}?\d+)?", point, sign);
+                quote!{ #regex.to_string() }
+            },
+            None if attr.auto_decimal => {
+                // both separators are matched, so mixing them is reported as ambiguous
+                let regex = format!(r"{0}?(?:\d|[\.,]\d)[\d\.,]*(?:[eE]{0}?\d+)?", sign);
+                quote!{ #regex.to_string() }
+            },
+            None if attr.decimal.is_some() => {
+                let regex = format!(r"{1}?(?:\d+(?:{0}\d*)?|{0}\d+)(?:[eE]{1}?\d+)?", point, sign);
+                quote!{ #regex.to_string() }
+            },
+            // regular expressions of numeric types write sign as `reformation::SIGN`
+            None if attr.unicode_minus => quote!{
+                ::reformation::non_capturing(<#ty as ::reformation::Reformation>::regex_str())
+                    .replace(::reformation::SIGN, #sign)
+            },
+            None => quote!{
+                ::reformation::non_capturing(<#ty as ::reformation::Reformation>::regex_str())
+            },
+        };
+        let mut transform = quote!{};
+        if let Some(grouping) = &attr.grouping{
+            let grouping = grouping.value();
+            transform = quote!{
+                #transform
+                let value: ::std::borrow::Cow<str> = value.replace(#grouping, "").into();
+            };
+        }
+        if let Some(decimal) = &attr.decimal{
+            let decimal = decimal.value().chars().next().unwrap();
+            transform = quote!{
+                #transform
+                let value: ::std::borrow::Cow<str> = value.replace(#decimal, ".").into();
+            };
+        }
+        if attr.auto_decimal{
+            transform = quote!{
+                #transform
+                if value.contains('.') && value.contains(','){
+                    return Err(format!("Value {} contains both `.` and `,`, so decimal separator is ambiguous", value).into());
+                }
+                let value: ::std::borrow::Cow<str> = value.replace(',', ".").into();
+            };
+        }
+        if attr.unicode_minus{
+            transform = quote!{
+                #transform
+                let value: ::std::borrow::Cow<str> = if value.contains('\u{2212}'){
+                    value.replace('\u{2212}', "-").into()
+                }else{
+                    value.into()
+                };
+            };
+        }
+        if attr.accounting{
+            regex = quote!{ format!(r"\((?:{0})\)|(?:{0})", #regex) };
+            transform = quote!{
+                #transform
+                let value: ::std::borrow::Cow<str> = if value.starts_with('(') && value.ends_with(')'){
+                    format!("-{}", &value[1..value.len() - 1]).into()
+                }else{
+                    value.into()
+                };
+            };
+        }
+        // zeros are stripped from normalized number, so grouping separators
+        // and custom decimal separator do not split it
+        if attr.allow_leading_zeros{
+            transform = quote!{
+                #transform
+                let value: ::std::borrow::Cow<str> = ::reformation::strip_leading_zeros(&value).into();
+            };
+        }
+        let parse = match attr.radix{
+            Some(radix) if attr.twos_complement => quote!{ ::reformation::parse_twos_complement::<#ty>(&value, #radix)? },
+            Some(radix) => quote!{ ::reformation::parse_radix::<#ty>(&value, #radix)? },
+            None => match attr.scale{
+                Some(scale) => {
+                    let scale = scale as usize;
+                    quote!{ ::reformation::parse_scaled::<#ty>(&value, #scale)? }
+                },
+                None => quote!{ value.parse::<#ty>()? },
+            },
+        };
+        // transformations above are applied in order: grouping, decimal separator,
+        // unicode minus, accounting parentheses, leading zeros, then radix prefix is stripped
+        // by `parse_radix`, and overflow of parsed value is clamped
+        let parse = if attr.clamp{
+            quote!{{
+                #[allow(clippy::needless_question_mark)]
+                let result = (||->Result<#ty, Box<dyn ::std::error::Error>>{ Ok(#parse) })();
+                match result{
+                    Ok(value) => value,
+                    Err(e) => match e.downcast_ref::<::std::num::ParseIntError>().map(|e| e.kind()){
+                        Some(::std::num::IntErrorKind::PosOverflow) => <#ty>::MAX,
+                        Some(::std::num::IntErrorKind::NegOverflow) => <#ty>::MIN,
+                        _ => return Err(e),
+                    },
                 }
+            }}
+        }else{
+            parse
+        };
+
+        Self{
+            name,
+            ty,
+            bound: None,
+            reuse: false,
+            named_group: false,
+            discarded: false,
+            ty_name: type_name(ty),
+            regex: quote!{ format!("({})", #regex) },
+            count: quote!{ 1 },
+            parse: quote!{{
+                let value = captures.get(offset).map(|x| x.as_str()).unwrap_or("");
+                #transform
+                (#parse, offset + 1)
+            }},
+        }
+    }
+}
+
+fn radix_prefix_regex(radix: u32)->&'static str{
+    match radix{
+        16 => "(?:0[xX])?",
+        8 => "(?:0[oO])?",
+        2 => "(?:0[bB])?",
+        _ => "",
+    }
+}
+
+fn radix_digits_regex(radix: u32)->String{
+    let digits = "0123456789abcdefghijklmnopqrstuvwxyz";
+    let last = digits.as_bytes()[radix as usize - 1] as char;
+    if radix <= 10{
+        format!("[0-{}]+", last)
+    }else{
+        format!("[0-9a-{0}A-{1}]+", last, last.to_ascii_uppercase())
+    }
+}
+
+
+/// Regular expression of type containing itself would be infinite,
+/// so `Box<Self>` fields are rejected with explanation.
+fn check_not_recursive<'a>(ds: &DeriveInput, fields: impl IntoIterator<Item=&'a Field>)->Result<(), TokenStream>{
+    let names = [ds.ident.clone(), Ident::new("Self", ds.ident.span())];
+    for field in fields{
+        if uses_type_params(&field.ty, &names){
+            let msg = format!(
+                "Field of recursive type `{}` cannot be parsed, since its regular expression would be infinite. Use generic parameter to nest values up to fixed depth: `{}<{}<T>>`.",
+                ds.ident, ds.ident, ds.ident
+            );
+            return Err(quote_spanned!{field.ty.span()=>
+                compile_error!{#msg}
+            });
+        }
+    }
+    Ok(())
+}
+
+fn get_fields(struct_: &DeriveInput)->Result<Vec<&Field>, TokenStream>{
+    if let Data::Struct(ref ds) = struct_.data{
+        let fields: Vec<_> = ds.fields.iter().collect();
+
+        if let Fields::Named(_) = ds.fields{
+            check_not_recursive(struct_, fields.iter().cloned())?;
+            Ok(fields)
+        }else{
+            Err(quote_spanned!{ds.fields.span()=>
+                compile_error!{"regex_parse supports only structs with named fields."}
+            })
+        }
+    }else{
+        Err(quote_spanned!{struct_.span()=>
+            compile_error!{"regex_parse supports only structs."}
+        })
+    }
+}
+
+
+/// Part of format string
+enum Segment{
+    /// regular expression, with `{{` and `}}` unescaped
+    Literal(String),
+    /// `{name}` placeholder
+    Field(String),
+    /// named capture group `(?P<name>...)`, written by user. Opening of group
+    /// is part of preceding literal.
+    Group(String),
+}
+
+/// split format string into literals and placeholders
+fn segments(format_string: &str)->Vec<Segment>{
+    let mut res = vec![];
+    let mut literal = String::new();
+    let mut iter = format_string.chars().peekable();
+    while let Some(c) = iter.next(){
+        match c{
+            '{' if iter.peek() == Some(&'{') => {
+                iter.next();
+                literal.push(c);
+            },
+            '}' if iter.peek() == Some(&'}') => {
+                iter.next();
+                literal.push(c);
             },
-            Some((i, c)) if c == '}' => {
-                if let Some(start) = curly_bracket_stack.pop(){
-                    let end = i;
-                    let substr = format_string.get(start..end).unwrap().to_string();
-                    map.insert(substr);
+            '{' => {
+                if !literal.is_empty(){
+                    res.push(Segment::Literal(std::mem::take(&mut literal)));
                 }
+                let arg: String = iter.by_ref().take_while(|c| *c != '}').collect();
+                res.push(Segment::Field(arg));
+            },
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty(){
+        res.push(Segment::Literal(literal));
+    }
+    res
+}
+
+/// Split literals after openings of named capture groups `(?P<name>` and `(?<name>`,
+/// so fields can be bound to them.
+fn bind_named_groups(segments: Vec<Segment>)->Vec<Segment>{
+    let mut res = vec![];
+    for segment in segments{
+        let literal = match segment{
+            Segment::Literal(literal) => literal,
+            other => {
+                res.push(other);
+                continue;
+            },
+        };
+        let mut current = String::new();
+        let mut chars = literal.chars().peekable();
+        let mut class_depth = 0;
+        while let Some(c) = chars.next(){
+            current.push(c);
+            match c{
+                '\\' => {
+                    if let Some(c) = chars.next(){
+                        current.push(c);
+                    }
+                },
+                '[' => class_depth += 1,
+                ']' if class_depth > 0 => class_depth -= 1,
+                '(' if class_depth == 0 && chars.peek() == Some(&'?') => {
+                    current.push(chars.next().unwrap());
+                    if chars.peek() == Some(&'P'){
+                        current.push(chars.next().unwrap());
+                    }
+                    if chars.peek() == Some(&'<'){
+                        current.push(chars.next().unwrap());
+                        let name: String = chars.by_ref().take_while(|c| *c != '>').collect();
+                        current.push_str(&name);
+                        current.push('>');
+                        res.push(Segment::Literal(std::mem::take(&mut current)));
+                        res.push(Segment::Group(name));
+                    }
+                },
+                _ => {},
+            }
+        }
+        if !current.is_empty(){
+            res.push(Segment::Literal(current));
+        }
+    }
+    res
+}
+
+/// parse which fields present in format string, in order of their first appearance
+fn arguments<'a>(segments: impl IntoIterator<Item=&'a Segment>)->Vec<&'a str>{
+    let mut args = vec![];
+    for segment in segments{
+        if let Segment::Field(arg) | Segment::Group(arg) = segment{
+            if !args.contains(&arg.as_str()){
+                args.push(arg.as_str());
+            }
+        }
+    }
+    args
+}
+
+/// escape regular expression special characters
+fn escape_regex(s: &str)->String{
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars(){
+        if r"\.+*?()|[]{}^$#&-~".contains(c){
+            res.push('\\');
+        }
+        res.push(c);
+    }
+    res
+}
+
+/// Format string in extended mode `(?x)`: each literal part and placeholder is
+/// written on its own line, placeholders are commented with field name and type.
+/// Regular expressions of fields are matched with extended mode disabled.
+fn verbose_format(format: &str, fields: &[FieldCode])->String{
+    let mut res = "(?x:\n".to_string();
+    for segment in segments(format){
+        match segment{
+            Segment::Literal(literal) => {
+                res.push_str(&format!("    {}\n", escape_format(&escape_verbose(&literal))));
+            },
+            Segment::Field(arg) | Segment::Group(arg) => {
+                let field = fields.iter().find(|f| field_name(f.name) == arg).unwrap();
+                let name = if field.discarded{ "_" }else{ &arg };
+                res.push_str(&format!("    (?-x:{{{}}})  # {}: {}\n", arg, name, field.ty_name));
+            },
+        }
+    }
+    res.push(')');
+    res
+}
+
+/// Escape whitespace and `#` in regular expression, so it matches the same
+/// strings in extended mode
+fn escape_verbose(regex: &str)->String{
+    let mut res = String::with_capacity(regex.len());
+    let mut chars = regex.chars();
+    while let Some(c) = chars.next(){
+        let c = match c{
+            '\\' => match chars.next(){
+                Some(c) if c.is_whitespace() => c,
+                Some(c) => {
+                    res.push('\\');
+                    res.push(c);
+                    continue;
+                },
+                None => c,
             },
-            Some(_) => {},
-            None => {break;}
+            c => c,
+        };
+        if c.is_whitespace() || c == '#'{
+            res.push_str(&format!("\\x{{{:X}}}", c as u32));
+        }else{
+            res.push(c);
+        }
+    }
+    res
+}
+
+/// escape format string special characters
+fn escape_format(s: &str)->String{
+    s.replace('{', "{{").replace('}', "}}")
+}
+
+/// type name without redundant spaces, produced by `quote`
+fn type_name(ty: &Type)->String{
+    let name = quote!(#ty).to_string();
+    let mut res = String::new();
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next(){
+        let drop = c == ' ' && (
+            res.ends_with(|c| "<&(:[".contains(c))
+            || chars.peek().map(|c| "<>),:]".contains(*c)).unwrap_or(true)
+        );
+        if !drop{
+            res.push(c);
         }
     }
-    map
+    res
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn test_escape_verbose(){
+        assert_eq!(escape_verbose(r"a b\ c"), r"a\x{20}b\x{20}c");
+        assert_eq!(escape_verbose("[^ #]\t"), r"[^\x{20}\x{23}]\x{9}");
+        assert_eq!(escape_verbose(r"\#\d+\\"), r"\#\d+\\");
+    }
+
+    #[test]
+    fn test_debug_description(){
+        let placeholders = vec![
+            ("{x}".to_string(), "u8".to_string()),
+            ("{y}".to_string(), "Option<i32>".to_string()),
+        ];
+        let expected = "reformation: Point: \\(<u8>, <Option<i32>>\\)\n    {x}: u8\n    {y}: Option<i32>\n";
+        assert_eq!(debug_description("Point", r"\(<u8>, <Option<i32>>\)", &placeholders), expected);
+        assert_eq!(debug_description("Shape::Empty", "Empty", &[]), "reformation: Shape::Empty: Empty\n");
+    }
+
+    #[test]
+    fn test_expand_template(){
+        let args = vec![
+            ("x".to_string(), "<u8>".to_string()),
+            ("_0".to_string(), "(.*)".to_string()),
+        ];
+        assert_eq!(expand_template(r"\{{{x}\}} {_0}", &args), r"\{<u8>\} (.*)");
+        assert_eq!(expand_template(r"a{{2}}", &args), r"a{2}");
+        assert_eq!(expand_template(r"{y}", &args), r"{y}");
+    }
 }
@@ -0,0 +1,144 @@
+use regex::Regex;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static::lazy_static!{
+    static ref CACHE: Mutex<HashMap<String, Regex>> = Mutex::new(HashMap::new());
+    static ref GENERIC_STR: Mutex<HashMap<TypeId, &'static str>> = Mutex::new(HashMap::new());
+    static ref GENERIC_REGEX: Mutex<HashMap<TypeId, &'static Regex>> = Mutex::new(HashMap::new());
+}
+
+/// Maximal number of patterns kept by `cached_regex`
+const CACHE_CAPACITY: usize = 1024;
+
+// Counter of compiled patterns is per thread, so that tests running in parallel
+// do not affect each other.
+#[cfg(test)]
+thread_local!{
+    static COMPILED: std::cell::Cell<usize> = const{ std::cell::Cell::new(0) };
+}
+
+/// Compile regular expression, or reuse previously compiled one with the same pattern.
+///
+/// Compiled automaton is shared between all clones of `Regex`, so types
+/// with identical patterns do not compile them twice.
+/// Cache keeps at most 1024 patterns: when it is full, it is cleared before
+/// inserting new one, so patterns built at runtime do not grow memory without bound.
+///
+/// ```
+/// let a = reformation::cached_regex(r"(\d+)").unwrap();
+/// let b = reformation::cached_regex(r"(\d+)").unwrap();
+/// assert_eq!(a.as_str(), b.as_str());
+/// ```
+pub fn cached_regex(pattern: &str)->Result<Regex, regex::Error>{
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(re) = cache.get(pattern){
+        return Ok(re.clone());
+    }
+    let re = Regex::new(pattern)?;
+    #[cfg(test)]
+    COMPILED.with(|x| x.set(x.get() + 1));
+    if cache.len() >= CACHE_CAPACITY{
+        cache.clear();
+    }
+    cache.insert(pattern.to_string(), re.clone());
+    Ok(re)
+}
+
+
+/// Regular expression string of generic type `T`, created by `init` on first call.
+///
+/// Statics declared inside of generic function are shared between all of its
+/// instantiations, so `Reformation::regex_str` of generic type should use this
+/// function instead of `lazy_static!`.
+///
+/// ```
+/// use reformation::{Reformation, Captures};
+/// use std::error::Error;
+///
+/// struct Pair<T>(T, T);
+///
+/// impl<T: Reformation + 'static> Reformation for Pair<T>{
+///     fn regex_str()->&'static str{
+///         reformation::generic_regex_str::<Self, _>(||{
+///             format!("{0}, {0}", T::regex_str())
+///         })
+///     }
+///
+///     fn captures_count()->usize{
+///         2 * T::captures_count()
+///     }
+///
+///     fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+///         let (a, offset) = T::from_captures_consuming(c, offset)?;
+///         let b = T::from_captures(c, offset)?;
+///         Ok(Pair(a, b))
+///     }
+/// }
+///
+/// assert_eq!(Pair::<u8>::regex_str(), r"(\d+), (\d+)");
+/// assert_eq!(Pair::<i8>::regex_str(), r"([\+-]?\d+), ([\+-]?\d+)");
+/// ```
+pub fn generic_regex_str<T: 'static, F: FnOnce()->String>(init: F)->&'static str{
+    let id = TypeId::of::<T>();
+    if let Some(s) = GENERIC_STR.lock().unwrap_or_else(|e| e.into_inner()).get(&id){
+        return s;
+    }
+    // lock is not held during `init`, since it most likely requests regular
+    // expression strings of other generic types.
+    let s = init();
+    let mut map = GENERIC_STR.lock().unwrap_or_else(|e| e.into_inner());
+    map.entry(id).or_insert_with(|| Box::leak(s.into_boxed_str()))
+}
+
+/// Compiled regular expression of generic type `T`, with pattern created by `init` on
+/// first call. Counterpart of `generic_regex_str`: later calls only look up `TypeId`,
+/// without building pattern or hashing it.
+///
+/// ```
+/// struct Wrapper<T>(T);
+///
+/// let re = reformation::generic_regex::<Wrapper<u8>, _>(|| r"(\d+)".to_string()).unwrap();
+/// assert!(re.is_match("42"));
+/// // pattern is not built again
+/// let re = reformation::generic_regex::<Wrapper<u8>, _>(|| unreachable!()).unwrap();
+/// assert_eq!(re.as_str(), r"(\d+)");
+/// ```
+pub fn generic_regex<T: 'static, F: FnOnce()->String>(init: F)->Result<&'static Regex, regex::Error>{
+    let id = TypeId::of::<T>();
+    if let Some(re) = GENERIC_REGEX.lock().unwrap_or_else(|e| e.into_inner()).get(&id){
+        return Ok(re);
+    }
+    // as in `generic_regex_str`, lock is not held during `init`
+    let re = cached_regex(&init())?;
+    let mut map = GENERIC_REGEX.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(map.entry(id).or_insert_with(|| Box::leak(Box::new(re))))
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    fn compiled()->usize{
+        COMPILED.with(|x| x.get())
+    }
+
+    #[test]
+    fn test_identical_patterns_compiled_once(){
+        let before = compiled();
+        let a = cached_regex(r"cache_test_(\d+)").unwrap();
+        let b = cached_regex(r"cache_test_(\d+)").unwrap();
+        assert_eq!(compiled(), before + 1);
+        assert!(a.is_match("cache_test_12") && b.is_match("cache_test_12"));
+
+        cached_regex(r"cache_test_(\w+)").unwrap();
+        assert_eq!(compiled(), before + 2);
+    }
+
+    #[test]
+    fn test_invalid_pattern(){
+        assert!(cached_regex(r"cache_test_(").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{Reformation, cached_regex, lazy_quantifiers, non_capturing};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::error::Error;
+use std::hash::Hash;
+
+/// Collections, which can be parsed from list of items separated by literal
+/// string, using `#[reformation(separator = "...")]` field attribute.
+pub trait Collection: Default{
+    type Item: Reformation;
+
+    /// Add item to collection. Returns `false` if item was not added
+    /// since collection already contains equal one.
+    fn insert_item(&mut self, item: Self::Item)->bool;
+}
+
+impl<T: Reformation> Collection for Vec<T>{
+    type Item = T;
+
+    fn insert_item(&mut self, item: T)->bool{
+        self.push(item);
+        true
+    }
+}
+
+impl<T: Reformation> Collection for VecDeque<T>{
+    type Item = T;
+
+    fn insert_item(&mut self, item: T)->bool{
+        self.push_back(item);
+        true
+    }
+}
+
+impl<T: Reformation + Eq + Hash> Collection for HashSet<T>{
+    type Item = T;
+
+    fn insert_item(&mut self, item: T)->bool{
+        self.insert(item)
+    }
+}
+
+impl<T: Reformation + Ord> Collection for BTreeSet<T>{
+    type Item = T;
+
+    fn insert_item(&mut self, item: T)->bool{
+        self.insert(item)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Collection for smallvec::SmallVec<A>
+    where A::Item: Reformation
+{
+    type Item = A::Item;
+
+    fn insert_item(&mut self, item: A::Item)->bool{
+        self.push(item);
+        true
+    }
+}
+
+/// Collections, which keep only one of equal items, so that policy for duplicates,
+/// `#[reformation(duplicates = "...")]` field attribute, applies to them.
+#[diagnostic::on_unimplemented(
+    message = "`duplicates` requires set, but `{Self}` keeps duplicate items",
+    label = "policy for duplicates of collection, which is not a set",
+)]
+pub trait Set: Collection{}
+
+impl<T: Reformation + Eq + Hash> Set for HashSet<T>{}
+
+impl<T: Reformation + Ord> Set for BTreeSet<T>{}
+
+/// Policy for separator after last item of list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingSeparator{
+    /// `1,2,3` only
+    Forbid,
+    /// both `1,2,3` and `1,2,3,`
+    Allow,
+    /// `1,2,3,` only
+    Require,
+}
+
+/// Policy for items already present in set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicates{
+    /// Silently drop duplicate
+    Dedupe,
+    /// Report error
+    Error,
+}
+
+/// Format of list of items, separated by literal string.
+///
+/// ```
+/// use reformation::{ListFormat, TrailingSeparator, Regex};
+///
+/// let format = ListFormat::new(", ").trailing_separator(TrailingSeparator::Allow);
+/// let re = Regex::new(&format!("^{}$", format.regex_str::<Vec<u8>>())).unwrap();
+/// let list = re.captures("1, 2, 3, ").unwrap();
+/// let list: Vec<u8> = format.parse(list.get(1).unwrap().as_str()).unwrap();
+/// assert_eq!(list, vec![1, 2, 3]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListFormat{
+    separator: &'static str,
+    trailing: TrailingSeparator,
+    duplicates: Duplicates,
+}
+
+impl ListFormat{
+    pub fn new(separator: &'static str)->Self{
+        Self{
+            separator,
+            trailing: TrailingSeparator::Forbid,
+            duplicates: Duplicates::Dedupe,
+        }
+    }
+
+    pub fn trailing_separator(mut self, trailing: TrailingSeparator)->Self{
+        self.trailing = trailing;
+        self
+    }
+
+    pub fn duplicates(mut self, duplicates: Duplicates)->Self{
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Regular expression matching whole list as single capture group
+    pub fn regex_str<C: Collection>(&self)->String{
+        let item = non_capturing(C::Item::regex_str());
+        let sep = regex::escape(self.separator);
+        match self.trailing{
+            TrailingSeparator::Forbid => format!("((?:(?:{0})(?:{1}(?:{0}))*)?)", item, sep),
+            TrailingSeparator::Allow => format!("((?:(?:{0})(?:{1}(?:{0}))*(?:{1})?)?)", item, sep),
+            TrailingSeparator::Require => format!("((?:(?:{0}){1})*)", item, sep),
+        }
+    }
+
+    /// Parse list matched by `regex_str`.
+    ///
+    /// Items are matched one by one, each followed by separator or end of list,
+    /// so items may contain separator themselves: `(1, 2), (3, 4)` with separator
+    /// `", "` is list of two pairs. Each item is the shortest text matching its type.
+    pub fn parse<C: Collection>(&self, list: &str)->Result<C, Box<dyn Error>>{
+        let mut res = C::default();
+        let item_re = lazy_quantifiers(C::Item::regex_str());
+        let re = cached_regex(&format!(r"\A(?:{})({}|\z)", item_re, regex::escape(self.separator)))?;
+        let separator_group = C::Item::captures_count() + 1;
+        let mut rest = list;
+        while !rest.is_empty(){
+            let captures = re.captures(rest)
+                .ok_or_else(|| format!("List item {:?} does not match format r{:?}", rest, C::Item::regex_str()))?;
+            let item = &rest[..captures.get(separator_group).unwrap().start()];
+            let inserted = res.insert_item(C::Item::from_captures(&captures, 1)?);
+            if !inserted && self.duplicates == Duplicates::Error{
+                return Err(format!("List contains duplicate item {:?}", item).into());
+            }
+            let end = captures.get(0).unwrap().end();
+            if end == 0{
+                return Err(format!("List item {:?} matches empty string with empty separator", rest).into());
+            }
+            rest = &rest[end..];
+        }
+        Ok(res)
+    }
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    fn parse(format: ListFormat, s: &str)->Option<Vec<i32>>{
+        let re = regex::Regex::new(&format!("^{}$", format.regex_str::<Vec<i32>>())).unwrap();
+        let captures = re.captures(s)?;
+        Some(format.parse(captures.get(1).unwrap().as_str()).unwrap())
+    }
+
+    #[test]
+    fn test_trailing_separator(){
+        let forbid = ListFormat::new(",");
+        let allow = forbid.trailing_separator(TrailingSeparator::Allow);
+        let require = forbid.trailing_separator(TrailingSeparator::Require);
+
+        assert_eq!(parse(forbid, "1,-2,3"), Some(vec![1, -2, 3]));
+        assert_eq!(parse(forbid, "1,-2,3,"), None);
+        assert_eq!(parse(forbid, ""), Some(vec![]));
+
+        assert_eq!(parse(allow, "1,-2,3"), Some(vec![1, -2, 3]));
+        assert_eq!(parse(allow, "1,-2,3,"), Some(vec![1, -2, 3]));
+        assert_eq!(parse(allow, ""), Some(vec![]));
+        assert_eq!(parse(allow, ","), None);
+
+        assert_eq!(parse(require, "1,-2,3"), None);
+        assert_eq!(parse(require, "1,-2,3,"), Some(vec![1, -2, 3]));
+        assert_eq!(parse(require, ""), Some(vec![]));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::Reformation;
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Deserialize string, then parse it into `Reformation` type using its `FromStr`
+/// implementation. Intended for use with `#[serde(deserialize_with = "reformation::deserialize")]`.
+///
+/// Requires feature `serde`.
+///
+/// ```
+/// use reformation::Reformation;
+/// use serde::Deserialize;
+///
+/// #[derive(Reformation, Debug)]
+/// #[reformation(r"{year}-{month}-{day}")]
+/// struct Date{
+///     year: u16,
+///     month: u8,
+///     day: u8,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Config{
+///     #[serde(deserialize_with = "reformation::deserialize")]
+///     start: Date,
+/// }
+///
+/// fn main(){
+///     let config: Config = serde_json::from_str(r#"{"start": "2018-12-22"}"#).unwrap();
+///     assert_eq!(config.start.year, 2018);
+/// }
+/// ```
+pub fn deserialize<'de, D, T>(deserializer: D)->Result<T, D::Error>
+    where D: Deserializer<'de>,
+          T: Reformation + FromStr,
+          T::Err: Display,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse().map_err(serde::de::Error::custom)
+}
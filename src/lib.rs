@@ -1,17 +1,53 @@
 //! Parsing via regular expressions using format syntax
 //!
 //! Deriving trait `Reformation` will also implement
-//! trait `FromStr`, with `Err=Box<Error>`
+//! trait `FromStr`, with `Err=Box<dyn Error>`. With argument `try_from` it also implements
+//! `TryFrom<&str>`, `TryFrom<String>` and `TryFrom<&String>` sharing the same error type
 //!
 //! Derive will require attribute reformation to specify format string,
 //! which will be treated as format string -> regular expression string
 //!
+//! Generated implementation is part of the crate defining the type, so it can be
+//! derived for `#[non_exhaustive]` structs and enums as well.
+//! Fields named with raw identifiers are referenced without prefix: `r#type` is `{type}`.
+//!
 //! Types implementing `Reformation` by default:
 //!
 //! + signed integers: `i8` `i16` `i32` `i64` `i128` `isize`
 //! + unsigned integers: `u8` `u16` `u32` `u64` `u128` `usize`
 //! + floats: `f32` `f64`
-//! + `String`
+//! + `String`, `Box<str>`
+//! + `CString`: any string without nul characters
+//! + non zero integers: `NonZeroU8` ... `NonZeroIsize`
+//! + `Option<T>`
+//! + `Result<T, E>`: value of `T`, or value of `E` if it does not match
+//! + transparent wrappers `Box<T>`, `Reverse<T>`, `Wrapping<T>`
+//! + `Ipv4Addr`: `127.0.0.1`, `Ipv6Addr`: `fe80::1`
+//! + `SocketAddrV4`: `127.0.0.1:8080`, `SocketAddrV6`: `[::1]:8080`, `SocketAddr`: either of them
+//! + `Duration`: number with unit `ns`, `us`, `ms`, `s`, `m`, `h` or `d`, possibly fractional: `1.5s`
+//! + ranges in rust syntax: `Range<T>` (`a..b`), `RangeFrom<T>` (`a..`), `RangeTo<T>` (`..b`)
+//! + `Bound<T>`: lower bound of interval `[a` (included), `(a` (excluded) or `(-inf` (unbounded)
+//! + tuples of up to 12 elements in rust syntax: `(a, b)`
+//!
+//! Ready-made types for common formats:
+//!
+//! + `AsciiChar` -- single ASCII character
+//! + `ClockTime` -- time of day `HH:MM:SS[.fraction]` as duration since midnight
+//! + `CompoundDuration` -- duration with multiple units `1h30m15s`
+//! + `SignedDuration` -- signed offset `±HH:MM[:SS]` in seconds: `-05:30`
+//! + `Sign` -- sign `+`, `-` or `±`, written separately from magnitude
+//! + `MacAddr` -- MAC address `01:23:45:67:89:ab` or `01-23-45-67-89-ab`
+//! + `PathComponents` -- path `/a/b/c` split into components on `/`
+//! + `Ip4Parsed`, `Ip6Parsed` -- IP address together with its octets or segments
+//! + `ByteSize` -- number of bytes with SI or IEC unit: `10MB`, `512KiB`
+//! + `Percent` -- percentage `50%` within `0..=100`, or bounds given by `range` attribute
+//! + `TriState` -- three valued logical value `true`, `false` or `unknown`
+//!
+//! Struct with derived `Reformation` can also be defined by `reformation_struct!` macro,
+//! taking format string after the struct.
+//!
+//! Types matching exact literal string can be declared with `literal!` macro,
+//! and tri-state types with custom tokens with `tri_state!` macro.
 //!
 //! ```
 //! use reformation::Reformation;
@@ -41,6 +77,12 @@
 //! Also they can be used for more flexible format strings.
 //! AVOID capture groups, since they would mess up with indexing of capture group
 //! generated by macro. use non-capturing groups `r"(?:)"` instead.
+//! The exception is named group `(?P<name>...)`: it binds field with the same name,
+//! which is parsed from text matched by the group instead of placeholder `{name}`.
+//! Such field must be of type matching single capture group (numbers, strings, etc.),
+//! otherwise parsing fails with error.
+//! Placeholder `{_:Type}` matches value of given type, which is checked but not
+//! stored in any field, and `{_}` matches any string the same way.
 //!
 //! ```
 //! use reformation::Reformation;
@@ -65,25 +107,487 @@
 //!     assert_eq!(v.z, 0.002);
 //! }
 //! ```
+//!
+//! Format string can be followed by additional arguments:
+//!
+//! + more format strings -- alternative formats, tried in order if previous ones do
+//!   not match: `#[reformation(r"{year}-{month}-{day}", r"{day}\.{month}\.{year}")]`.
+//!   Each alternative must reference all the same fields.
+//! + `error = Type` -- error type of generated `FromStr` implementation, see `ParseError`.
+//! + `prefix = "..."`, `suffix = "..."` -- literal strings surrounding format. Unlike
+//!   format string they are not regular expressions, so they need no escaping.
+//! + `canonical` -- generate method `canonical(&self)->String`, rendering value
+//!   with (first) format string: fields are written using `Display`, optional
+//!   whitespace (`\s*`) is omitted and required one (`\s+`) is written as single space.
+//! + `try_parse_ref` -- generate method `try_parse_ref`, which reports failure
+//!   with `NoRegexMatchRef` borrowing the input instead of allocating. Only unmatched
+//!   input is reported without allocation: input containing invalid value allocates
+//!   capture slots and error of the field, which is discarded.
+//! + `is` -- generate method `is(input: &str, expected: &Self)->bool`, checking whether
+//!   input parses to value equal to expected one. Requires `Self: PartialEq`.
+//! + `reparse` -- generate method `reparse(&mut self, input: &str)`, parsing into existing
+//!   value. `String` fields parsed as is (possibly with `max_len`) are copied into their
+//!   existing buffers, so repeated calls on similar records do not allocate strings.
+//!   Value is left unchanged if parsing fails. Cannot be combined with alternative formats.
+//! + `nom` -- generate method `parse_nom(input: &str)->IResult<&str, Self>`, usable
+//!   as `nom` parser. Requires feature `nom`.
+//! + `raw` -- format string is regular expression used as is, without `{field}`
+//!   placeholders. Each field is parsed from named capture group `(?P<field>...)`,
+//!   so field types must capture single group (numbers, strings, etc.). Field of type
+//!   `Option<T>` is `None` if its group did not participate in match.
+//! + `unicode = false` -- disable Unicode mode of regular expression: `\d`, `\w`, `\s`
+//!   match only ASCII characters, which is faster on ASCII only data. See `ascii_regex`.
+//! + `greedy = false` -- repetitions (`*`, `+`, `{n,m}`) in regular expressions of fields
+//!   are made lazy, so each field matches as little as possible. Literal parts of format
+//!   string are not affected. Mostly useful with anchored formats: `^{key}:{value}$`
+//!   splits `a:b:c` at the first colon rather than the last.
+//! + `string_default = "greedy" | "lazy" | "non_ws"` -- regular expression of `String` and
+//!   `Option<String>` fields without attributes changing it (and of borrowed `&str` fields of
+//!   `ReformationRef`): `(.*)` (default of `String`), `(.*?)` or `(\S+)`.
+//!   Takes precedence over `greedy = false`.
+//! + `word_start`, `word_end`, `word_boundaries` -- match must start, end or both start
+//!   and end at word boundary `\b`, so that value is not found inside of longer word.
+//!   Boundary lies between word and non-word character, so format should start (end)
+//!   with word character.
+//! + `verbose_pattern` -- `regex_str` (and `explain`) is regular expression in extended
+//!   mode `(?x)`, with each part of format on its own line and placeholders commented
+//!   with field name and type. Whitespace and `#` in format string are escaped, so
+//!   the same inputs are matched.
+//! + `try_from` -- implement `TryFrom<&str>`, `TryFrom<String>` and `TryFrom<&String>`
+//!   along with `FromStr`. Also accepted by enum attribute.
+//!
+//! ```
+//! use reformation::Reformation;
+//!
+//! #[derive(Reformation, Debug)]
+//! #[reformation(r"{x}, {y}", prefix = "[(", suffix = ")]")]
+//! struct Point{
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! fn main(){
+//!     let p: Point = "[(3, -4)]".parse().unwrap();
+//!     assert_eq!((p.x, p.y), (3, -4));
+//! }
+//! ```
+//!
+//! Reformation can also be derived for enums. Each variant matches format string
+//! given in its attribute. Unit variant without attribute matches its own name.
+//! Fields of tuple variants are referenced by position: `{}` or `{0}`. Variants
+//! with more fields are tried first. If every variant is a single character,
+//! the whole enum is matched by character class (`[MF]`).
+//! With enum attribute `#[reformation(ignore_case_fields)]` literal parts of variant
+//! formats, such as names of unit variants, are case insensitive, while regular
+//! expressions of fields are not affected.
+//! Unit variant marked with `#[reformation(empty)]` matches empty string, so absent
+//! token is parsed as this variant instead of failing. It is tried after all other variants.
+//!
+//! Regular expressions have no lookahead, so single alternation cannot tell `4` of
+//! `Int(i64)` from `4.5` of `Float(f64)` when parsing unanchored input. With enum attribute
+//! `#[reformation(sequential)]` `FromStr` and `Reformation::parse_opt` instead try variants
+//! in order of declaration, each with its own regular expression matching the whole input,
+//! and return the first one, which matches and parses successfully. `Reformation::regex_str`
+//! is still single alternation (in order of declaration), so enum embedded as field of
+//! another struct is still parsed by plain alternation: there `Int` matches prefix `4`
+//! of `4.5`, unless the rest of format string cannot match after it.
+//!
+//! ```
+//! use reformation::Reformation;
+//!
+//! #[derive(Reformation, Debug, PartialEq)]
+//! enum Gender{
+//!     #[reformation("M")]
+//!     Male,
+//!     #[reformation("F")]
+//!     Female,
+//! }
+//!
+//! fn main(){
+//!     assert_eq!("F".parse::<Gender>().unwrap(), Gender::Female);
+//!     assert!("X".parse::<Gender>().is_err());
+//! }
+//! ```
+//!
+//! Enum with integer `#[repr(...)]` marked with `#[reformation(repr)]` is parsed from
+//! numeric value of its discriminant instead, rejecting unknown values.
+//!
+//! ```
+//! use reformation::Reformation;
+//!
+//! #[derive(Reformation, Debug, PartialEq)]
+//! #[reformation(repr)]
+//! #[repr(u8)]
+//! enum Opcode{
+//!     Nop = 0,
+//!     Load = 0x10,
+//! }
+//!
+//! fn main(){
+//!     assert_eq!("16".parse::<Opcode>().unwrap(), Opcode::Load);
+//!     assert!("1".parse::<Opcode>().is_err());
+//! }
+//! ```
+//!
+//! If environment variable `REFORMATION_DEBUG=1` is set during compilation, derive
+//! prints regular expression of each struct and enum variant, and placeholders with
+//! their types. Regular expressions of field types are only known at runtime, so they
+//! are shown as `<Type>`, and complete one is shown by `Reformation::explain`. Cargo does
+//! not rebuild crate when environment variable changes, so run `cargo clean -p <crate>`
+//! (or touch its sources) before building with it.
+//!
+//! Derive `ReformationRef` is alternative for structs with `&'a str` (or `Option<&'a str>`)
+//! fields, which are borrowed from input instead of being copied into `String`. It does
+//! not implement `Reformation` and `FromStr`, which cannot return borrowed values, but
+//! generates inherent methods `regex_str()` and `parse(input: &'a str)`. Other fields
+//! are parsed as usual, and accept field attributes. Prefer it for hot loops over large
+//! inputs, where strings are only inspected, and owning derive otherwise, since
+//! borrowed struct cannot outlive input or be nested into other `Reformation` types.
+//! Struct arguments `prefix`, `suffix`, `greedy` and `unicode` are supported.
+//!
+//! ```
+//! use reformation::ReformationRef;
+//!
+//! #[derive(ReformationRef)]
+//! #[reformation(r"^{method} {path} HTTP/1\.{minor}$")]
+//! struct Request<'a>{
+//!     method: &'a str,
+//!     path: &'a str,
+//!     minor: u8,
+//! }
+//!
+//! fn main(){
+//!     let request = Request::parse("GET /index.html HTTP/1.1").unwrap();
+//!     assert_eq!((request.method, request.path, request.minor), ("GET", "/index.html", 1));
+//! }
+//! ```
+//!
+//! `Tokenizer` consumes values of `Reformation` types one after another, which
+//! is handy for hand written parsers of simple grammars. Macro `scan!` parses
+//! sequence of whitespace separated values with it: `scan!(input, u32, Date)`.
+//!
+//! With feature `serde` enabled, `deserialize` can be used to deserialize fields
+//! from strings: `#[serde(deserialize_with = "reformation::deserialize")]`.
+//!
+//! With feature `nom` enabled, `parse_nom::<T>` is `nom` parser consuming value of `T`
+//! from the start of input, and struct argument `nom` generates the same parser as
+//! method `Self::parse_nom`.
+//!
+//! Parsing of individual fields can be tuned with field attribute `#[reformation(...)]`.
+//! Arguments of struct, enum or field can be split between several `#[reformation(...)]`
+//! attributes, placed in any order among other attributes:
+//!
+//! + `radix = N` -- parse integer in given radix. Prefixes `0x`, `0o`, `0b` are allowed
+//!   for radix 16, 8, 2 respectively.
+//! + `twos_complement` -- together with `radix`, digits are bits of signed integer
+//!   in two's complement representation of its width: `0xff` is `-1i8`.
+//! + `accounting` -- negative number can be written in parentheses: `(42)` is `-42`.
+//! + `unicode_minus` -- accept unicode minus sign `−` (U+2212) along with ascii `-`.
+//! + `default_if_absent` -- if field is placed inside of optional group, which did not
+//!   participate in match, `Default::default()` is used as its value.
+//! + `default = "expr"` -- value of field, which does not appear in format string, or
+//!   is placed inside of optional group, which did not participate in match. String
+//!   literal is converted with `From`, so `default = "\"none\""` works for `String` field.
+//! + `separator = ","` -- field is collection (such as `Vec<T>` or `VecDeque<T>`) of items, separated by
+//!   given string. Separator after last item is forbidden by default, and can be
+//!   allowed or required with `trailing_separator = "allow"` / `"require"`.
+//!   Sets (`HashSet<T>`, `BTreeSet<T>`) silently drop duplicate items, unless `duplicates = "error"`
+//!   is specified. `duplicates` on collection, which is not a set, is compile error.
+//!   With feature `smallvec` enabled, `SmallVec<[T; N]>` can be used as well.
+//! + `decimal = ","` -- floating point number uses given character as decimal separator:
+//!   `3,14` is `3.14`. Note that digit grouping is not accepted, so `1,000` is `1.0`.
+//! + `auto_decimal` -- floating point number uses either `.` or `,` as decimal separator:
+//!   both `3.14` and `3,14` are `3.14`, while `3,1.4` is rejected as ambiguous.
+//! + `allow_leading_zeros` -- leading zeros of numbers are removed before parsing, so
+//!   `Ipv4Addr` accepts `010.000.001.001`. Fractional part of decimal number is kept
+//!   as is: `007.05` is `7.05`.
+//! + `checked` -- integer overflow is reported with error naming the field, its value and type.
+//! + `clamp` -- integer overflow gives minimal or maximal value of type: `300` is `255u8`.
+//!   Cannot be combined with `checked`.
+//! + `max_len = N` -- `String` or `Option<String>` field matches at most `N` characters: `(.{0,N})`.
+//! + `flag = "--verbose"` -- `bool` field is `true` if given literal is present, and
+//!   `false` otherwise.
+//! + `count = 'a'` -- `usize` field is number of consecutive repetitions of given
+//!   character: `aaab` matched by `{n}b` gives `3`, and empty match gives `0`.
+//! + `count_of = r"X"` -- `usize` field matches any text (`(.*)`), and is number of
+//!   non-overlapping matches of regular expression `X` within it: `[EWE]` matched by
+//!   `\[{errors}\]` with `count_of = "E"` gives `2`.
+//! + `or = "expr"` -- `Option<T>` field is `Some(expr)` instead of `None`, if its group
+//!   did not participate in match.
+//! + `when = "expr"` -- field is present only if condition holds. Condition may refer
+//!   to fields placed before this one in format string, since fields are parsed in
+//!   order of appearance. Placeholder of the field should be inside of optional group:
+//!   if condition holds, the group must participate in match, otherwise it must not,
+//!   and `Default::default()` is used as value (`None` for `Option<T>`). Regular
+//!   expression itself does not depend on condition.
+//! + `capture = "whole"` -- field, which does not appear in format string, receives
+//!   whole text matched by regular expression (`String` or other type convertible
+//!   from `&str`). Note that for nested types it is the match of the outermost one.
+//! + `reuse` -- field may appear in format string multiple times. Every occurrence is
+//!   parsed, and all of them must be equal. Without this attribute repeated placeholder
+//!   is compile error.
+//! + `grouping = ","` -- number uses given character as thousands separator: `1,234,567`.
+//!   Separators must split integer part into groups of three digits, or be absent.
+//!   Fractional part is accepted only together with `decimal` (or `strict_float`):
+//!   `#[reformation(grouping = ",", decimal = ".")]` parses `1,234,567.89`.
+//! + `strict_float` -- decimal point of floating point number must have digits on both
+//!   sides: `5.0` and `0.5` are accepted, while `5.` and `.5` are not.
+//! + `scale = N` -- integer field stores decimal number multiplied by `10^N`, for fixed
+//!   point values: with `scale = 2` `3.14` is `314` and `3.1` is `310`. Number with more
+//!   than `N` fractional digits is rejected, rather than rounded or truncated.
+//! + `range = "0.0..=1.0"` -- parsed number must be within range, written in rust syntax
+//!   (`a..b`, `a..=b`, `a..`, `..b`). Value out of range, or `NaN`, is reported as error,
+//!   naming the field. Field type must implement `RangeBound`: `f32`, `f64` and `Percent`,
+//!   but not integers. Range replaces default bounds of type, which has them: for `Percent`
+//!   it replaces `0..=100`.
+//! + `digits = "19..=39"` -- number of decimal digits of integer must be within range,
+//!   checked before parsing. Handy for long identifiers stored in `u128`.
+//! + `validate = path::to_predicate` -- after parsing, check value with function
+//!   `fn(&T)->bool` or `fn(&T)->Result<(), E>`. Failed check is reported as error,
+//!   naming the field.
+//!
+//! Number format attributes are applied in order: grouping separators are removed, decimal
+//! separator and unicode minus replaced, accounting parentheses turned into minus sign,
+//! leading zeros stripped, then radix prefix is stripped and number is parsed, and finally
+//! overflow is clamped (`clamp`) or reported (`checked`), and `range` is checked. So
+//! `#[reformation(accounting, radix = 16, clamp)]` parses `(0x7f)` as `-127` and `(0xff)`
+//! as `-128` for `i8`. `twos_complement` cannot be combined with `accounting` or `clamp`.
+//!
+//! ```
+//! use reformation::Reformation;
+//!
+//! #[derive(Reformation, Debug)]
+//! #[reformation(r"{code}: {balance}")]
+//! struct Record{
+//!     #[reformation(radix = 16)]
+//!     code: u32,
+//!     #[reformation(accounting)]
+//!     balance: i64,
+//! }
+//!
+//! fn main(){
+//!     let r: Record = "0xff: (300)".parse().unwrap();
+//!     assert_eq!(r.code, 255);
+//!     assert_eq!(r.balance, -300);
+//! }
+//! ```
+
+
+/// Character class of sign of number in regular expressions of numeric types
+macro_rules! sign{
+    () => { r"[\+-]" };
+}
 
+mod cache;
+mod collection;
+#[cfg(feature = "serde")]
+mod de;
+#[cfg(feature = "nom")]
+mod nom_compat;
+mod tokenizer;
+mod types;
 
 pub use reformation_derive::*;
+pub use cache::{cached_regex, generic_regex, generic_regex_str};
+pub use collection::{Collection, Set, ListFormat, TrailingSeparator, Duplicates};
+#[cfg(feature = "serde")]
+pub use de::deserialize;
+#[cfg(feature = "nom")]
+pub use nom_compat::parse_nom;
+#[cfg(feature = "nom")]
+pub use nom;
+pub use tokenizer::Tokenizer;
+pub use types::*;
 
 use std::fmt;
 use std::error::Error;
-pub use regex::{Regex, Captures};
+use std::ffi::CString;
+use std::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize};
+use std::cmp::Reverse;
+use std::convert::TryFrom;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::Wrapping;
+use std::num::{NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize};
+use std::ops::{Bound, Range, RangeFrom, RangeTo};
+use std::str::FromStr;
+use std::time::Duration;
+pub use regex::{Regex, Captures, escape};
 pub use lazy_static::lazy_static;
 
+/// Character class of sign of number in regular expressions of numeric types.
+/// Derived implementation replaces it to accept unicode minus with `unicode_minus`.
+#[doc(hidden)]
+pub const SIGN: &str = sign!();
+
 #[derive(Debug)]
 pub struct NoRegexMatch{
     pub format: &'static str,
     pub request: String,
 }
 
+impl NoRegexMatch{
+    /// Line and column of position in input, where matching most likely failed.
+    /// Computed on request by matching prefixes of regular expression, see `failure_position`.
+    pub fn line_column(&self)->(usize, usize){
+        line_column(&self.request, failure_position(self.format, &self.request))
+    }
+}
+
 impl std::error::Error for NoRegexMatch{}
 impl fmt::Display for NoRegexMatch{
     fn fmt(&self, f: &mut fmt::Formatter)->fmt::Result{
-        write!(f, "String {:?} does not match format r{:?}", self.format, self.request)
+        write!(f, "String {:?} does not match format r{:?}", self.request, self.format)
+    }
+}
+
+/// Borrowed counterpart of `NoRegexMatch`, referencing input instead of copying it.
+///
+/// Returned by `try_parse_ref` method, generated with
+/// `#[reformation(r"...", try_parse_ref)]`. Creating it does not allocate,
+/// which is useful for tools reporting many errors.
+/// Input containing invalid values is reported same way as unmatched input,
+/// but detecting it allocates capture slots and error of the field.
+///
+/// ```
+/// use reformation::Reformation;
+///
+/// #[derive(Reformation, Debug)]
+/// #[reformation(r"{x}:{y}", try_parse_ref)]
+/// struct Point{
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// fn main(){
+///     let input = String::from("1-2");
+///     let err = Point::try_parse_ref(&input).unwrap_err();
+///     assert_eq!(err.request, "1-2");
+///     assert_eq!(Point::try_parse_ref("1:2").unwrap().y, 2);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoRegexMatchRef<'a>{
+    pub format: &'static str,
+    pub request: &'a str,
+}
+
+impl<'a> NoRegexMatchRef<'a>{
+    /// Line and column of position in input, where matching most likely failed.
+    /// Computed on request by matching prefixes of regular expression, see `failure_position`.
+    pub fn line_column(&self)->(usize, usize){
+        line_column(self.request, failure_position(self.format, self.request))
+    }
+}
+
+impl<'a> std::error::Error for NoRegexMatchRef<'a>{}
+impl<'a> fmt::Display for NoRegexMatchRef<'a>{
+    fn fmt(&self, f: &mut fmt::Formatter)->fmt::Result{
+        write!(f, "String {:?} does not match format r{:?}", self.request, self.format)
+    }
+}
+
+impl<'a> From<NoRegexMatchRef<'a>> for NoRegexMatch{
+    fn from(e: NoRegexMatchRef<'a>)->Self{
+        NoRegexMatch{format: e.format, request: e.request.to_string()}
+    }
+}
+
+/// Detailed parsing error, keeping both input and the reason of failure.
+#[derive(Debug)]
+pub enum ReformationError{
+    /// Input does not match regular expression of type.
+    NoRegexMatch(NoRegexMatch),
+    /// Input matches regular expression, but captured value could not be parsed.
+    InvalidValue{
+        format: &'static str,
+        request: String,
+        error: Box<dyn Error>,
+    },
+}
+
+impl std::error::Error for ReformationError{
+    fn source(&self)->Option<&(dyn Error + 'static)>{
+        match self{
+            ReformationError::NoRegexMatch(e) => Some(e),
+            ReformationError::InvalidValue{error, ..} => Some(error.as_ref()),
+        }
+    }
+}
+
+impl fmt::Display for ReformationError{
+    fn fmt(&self, f: &mut fmt::Formatter)->fmt::Result{
+        match self{
+            ReformationError::NoRegexMatch(e) => e.fmt(f),
+            ReformationError::InvalidValue{format, request, error} => {
+                write!(f, "String {:?} matches format r{:?}, but contains invalid value: {}", request, format, error)
+            }
+        }
+    }
+}
+
+impl From<NoRegexMatch> for ReformationError{
+    fn from(e: NoRegexMatch)->Self{
+        ReformationError::NoRegexMatch(e)
+    }
+}
+
+/// Error type of derived `FromStr` implementation.
+///
+/// Defaults to `Box<dyn Error>`, and can be changed with
+/// `#[reformation(r"...", error = Type)]`.
+///
+/// ```
+/// use reformation::{Reformation, NoRegexMatch};
+///
+/// #[derive(Reformation, Debug)]
+/// #[reformation(r"{x}:{y}", error = NoRegexMatch)]
+/// struct Point{
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// fn main(){
+///     let err: NoRegexMatch = "1-2".parse::<Point>().unwrap_err();
+///     assert_eq!(err.request, "1-2");
+/// }
+/// ```
+pub trait ParseError: Sized{
+    /// input does not match regular expression `format`
+    fn no_match(format: &'static str, request: &str)->Self;
+
+    /// input matches regular expression `format`, but `from_captures` failed
+    fn invalid_value(format: &'static str, request: &str, error: Box<dyn Error>)->Self;
+}
+
+impl ParseError for Box<dyn Error>{
+    fn no_match(format: &'static str, request: &str)->Self{
+        Box::new(NoRegexMatch{format, request: request.to_string()})
+    }
+
+    fn invalid_value(_format: &'static str, _request: &str, error: Box<dyn Error>)->Self{
+        error
+    }
+}
+
+/// Lightweight error, which does not keep reason of failure.
+/// Input containing invalid values is reported same way as unmatched input.
+impl ParseError for NoRegexMatch{
+    fn no_match(format: &'static str, request: &str)->Self{
+        NoRegexMatch{format, request: request.to_string()}
+    }
+
+    fn invalid_value(format: &'static str, request: &str, _error: Box<dyn Error>)->Self{
+        NoRegexMatch{format, request: request.to_string()}
+    }
+}
+
+impl ParseError for ReformationError{
+    fn no_match(format: &'static str, request: &str)->Self{
+        ReformationError::NoRegexMatch(NoRegexMatch::no_match(format, request))
+    }
+
+    fn invalid_value(format: &'static str, request: &str, error: Box<dyn Error>)->Self{
+        ReformationError::InvalidValue{format, request: request.to_string(), error}
     }
 }
 
@@ -98,7 +602,137 @@ pub trait Reformation: Sized{
     fn captures_count()->usize;
 
     /// create instance of function from captures with given offset
-    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<Error>>;
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>;
+
+    /// create instance of function from captures with given offset, and return
+    /// it along with offset of first capture group after ones used by it.
+    ///
+    /// ```
+    /// use reformation::{Reformation, Regex};
+    ///
+    /// let re = Regex::new(&format!("{} {}", i32::regex_str(), f32::regex_str())).unwrap();
+    /// let captures = re.captures("-3 2.5").unwrap();
+    /// let (x, offset) = i32::from_captures_consuming(&captures, 1).unwrap();
+    /// let (y, offset) = f32::from_captures_consuming(&captures, offset).unwrap();
+    /// assert_eq!((x, y, offset), (-3, 2.5, 3));
+    /// ```
+    fn from_captures_consuming(c: &Captures, offset: usize)->Result<(Self, usize), Box<dyn Error>>{
+        let value = Self::from_captures(c, offset)?;
+        Ok((value, offset + Self::captures_count()))
+    }
+
+    /// Lower bound of length of matched string. Derived implementation counts
+    /// literal characters of format string, which can serve as capacity hint
+    /// for batch parsing: `input.len() / T::min_len().max(1)`.
+    ///
+    /// ```
+    /// use reformation::Reformation;
+    ///
+    /// #[derive(Reformation)]
+    /// #[reformation(r"{x}:{y}(?: \({comment}\))?")]
+    /// struct Point{
+    ///     x: u8,
+    ///     y: u8,
+    ///     comment: Option<String>,
+    /// }
+    ///
+    /// fn main(){
+    ///     assert_eq!(Point::min_len(), 1);
+    /// }
+    /// ```
+    fn min_len()->usize{
+        0
+    }
+
+    /// Human readable description of regular expression. Derived implementation
+    /// lists literal parts of format string, and fields with their regular
+    /// expressions and capture groups.
+    ///
+    /// ```
+    /// use reformation::Reformation;
+    ///
+    /// #[derive(Reformation)]
+    /// #[reformation(r"{x}:{y}")]
+    /// struct Point{
+    ///     x: u8,
+    ///     y: i8,
+    /// }
+    ///
+    /// fn main(){
+    ///     let expected = r#"Point: r"(\\d+):([\\+-]?\\d+)"
+    ///     {x}: u8 = r"(\\d+)", groups 1..2
+    ///     literal r":"
+    ///     {y}: i8 = r"([\\+-]?\\d+)", groups 2..3
+    /// "#;
+    ///     assert_eq!(Point::explain(), expected);
+    /// }
+    /// ```
+    fn explain()->String{
+        format!("r{:?}\n", Self::regex_str())
+    }
+
+    /// Show input with marker `^` under position, where matching most likely failed.
+    /// Position is furthest end of match of any prefix of regular expression (with
+    /// open groups closed), so for matching input marker points at the end of match.
+    ///
+    /// ```
+    /// use reformation::Reformation;
+    ///
+    /// #[derive(Reformation, Debug)]
+    /// #[reformation(r"{year}-{month}-{day}")]
+    /// struct Date{
+    ///     year: u16,
+    ///     month: u8,
+    ///     day: u8,
+    /// }
+    ///
+    /// fn main(){
+    ///     assert_eq!(Date::explain_failure("2018-1x-22"), "2018-1x-22\n      ^");
+    /// }
+    /// ```
+    fn explain_failure(input: &str)->String{
+        let position = failure_position(Self::regex_str(), input);
+        let column = input[..position].chars().count();
+        format!("{}\n{}^", input, " ".repeat(column))
+    }
+
+    /// Parse input, for control flow where mismatch is expected rather than error.
+    /// Returns `None` on any failure: both if input does not match regular expression
+    /// and if matched value of some field cannot be parsed. Use `FromStr` to tell
+    /// them apart.
+    ///
+    /// Derived implementations override it to use their own compiled regular expression,
+    /// while default one looks it up by `regex_str` in `cached_regex` on each call.
+    ///
+    /// ```
+    /// use reformation::Reformation;
+    ///
+    /// #[derive(Reformation, Debug, PartialEq)]
+    /// #[reformation(r"^{x}:{y}$")]
+    /// struct Point{
+    ///     x: u8,
+    ///     y: u8,
+    /// }
+    ///
+    /// fn main(){
+    ///     assert_eq!(Point::parse_opt("3:4"), Some(Point{x: 3, y: 4}));
+    ///     assert_eq!(Point::parse_opt("3;4"), None);
+    ///     // 300 matches regular expression, but does not fit into `u8`
+    ///     assert_eq!(Point::parse_opt("300:4"), None);
+    /// }
+    /// ```
+    fn parse_opt(input: &str)->Option<Self>{
+        let re = cached_regex(Self::regex_str()).ok()?;
+        let captures = re.captures(input)?;
+        Self::from_captures(&captures, 1).ok()
+    }
+
+    /// Create instance from captures without checking default bounds of value,
+    /// used by `range` field attribute, which replaces them.
+    #[doc(hidden)]
+    fn from_captures_unbounded(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Self::from_captures(c, offset)
+    }
 }
 
 
@@ -117,7 +751,7 @@ macro_rules! group_impl_parse_primitive{
                 1
             }
 
-            fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<std::error::Error>>{
+            fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn std::error::Error>>{
                 let res = c.get(offset).unwrap().as_str().parse::<$name>()?;
                 Ok(res)
             }
@@ -126,10 +760,975 @@ macro_rules! group_impl_parse_primitive{
 }
 
 group_impl_parse_primitive!{r"(\d+)", u8, u16, u32, u64, u128, usize}
-group_impl_parse_primitive!{r"([\+-]?\d+)", i8, i16, i32, i64, i128, isize}
-group_impl_parse_primitive!{r"((?:[\+-]?\d+(?:.\d*)?|.\d+)(?:[eE][\+-]?\d+)?)", f32, f64}
+group_impl_parse_primitive!{concat!("(", sign!(), r"?\d+)"), i8, i16, i32, i64, i128, isize}
+group_impl_parse_primitive!{concat!("((?:", sign!(), r"?\d+(?:\.\d*)?|\.\d+)(?:[eE]", sign!(), r"?\d+)?)"), f32, f64}
 group_impl_parse_primitive!{r"(.*)", String}
 
+/// Owned string without spare capacity, matches the same as `String`
+impl Reformation for Box<str>{
+    fn regex_str()->&'static str{
+        String::regex_str()
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(c.get(offset).unwrap().as_str().into())
+    }
+}
+
+/// String without interior nul characters, for passing to C code.
+/// Since nul cannot be matched, it also ends the match.
+impl Reformation for CString{
+    fn regex_str()->&'static str{
+        r"([^\x00]*)"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(CString::new(c.get(offset).unwrap().as_str())?)
+    }
+}
+
+group_impl_parse_primitive!{r"(\d+)", NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize}
+group_impl_parse_primitive!{concat!("(", sign!(), r"?\d+)"), NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize}
+
+
+/// Optional value. Matches either value of `T` or empty string.
+///
+/// Value is `None` only if its group did not participate in match. Error of
+/// parsing matched value, such as `0` for `Option<NonZeroU32>`, is reported
+/// rather than turned into `None`.
+/// If `T` can match empty string, such match results in `Some`:
+///
+/// ```
+/// use reformation::Reformation;
+///
+/// #[derive(Reformation, Debug)]
+/// #[reformation(r"^{id};(?:name={name})?$")]
+/// struct User{
+///     id: Option<u32>,
+///     name: Option<String>,
+/// }
+///
+/// fn main(){
+///     // optional group with name did not participate
+///     let user: User = ";".parse().unwrap();
+///     assert_eq!(user.id, None);
+///     assert_eq!(user.name, None);
+///     // name matched empty string
+///     let user: User = "12;name=".parse().unwrap();
+///     assert_eq!(user.id, Some(12));
+///     assert_eq!(user.name, Some(String::new()));
+/// }
+/// ```
+impl<T: Reformation + 'static> Reformation for Option<T>{
+    fn regex_str()->&'static str{
+        generic_regex_str::<Self, _>(|| format!("({})?", T::regex_str()))
+    }
+
+    fn captures_count()->usize{
+        T::captures_count() + 1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        if c.get(offset).is_some(){
+            Ok(Some(T::from_captures(c, offset + 1)?))
+        }else{
+            Ok(None)
+        }
+    }
+}
+
+/// Either value of `T`, or (if it does not match) value of `E`.
+/// With `E = String` text, which is not valid `T`, is kept as is:
+///
+/// ```
+/// use reformation::Reformation;
+///
+/// #[derive(Reformation, Debug)]
+/// #[reformation(r"^{name}: {age}$")]
+/// struct Person{
+///     name: String,
+///     age: Result<u8, String>,
+/// }
+///
+/// fn main(){
+///     let p: Person = "Bob: 42".parse().unwrap();
+///     assert_eq!(p.age, Ok(42));
+///     let p: Person = "Alice: unknown".parse().unwrap();
+///     assert_eq!(p.age, Err("unknown".to_string()));
+/// }
+/// ```
+impl<T: Reformation + 'static, E: Reformation + 'static> Reformation for Result<T, E>{
+    fn regex_str()->&'static str{
+        generic_regex_str::<Self, _>(|| format!("(?:({})|({}))", T::regex_str(), E::regex_str()))
+    }
+
+    fn captures_count()->usize{
+        T::captures_count() + E::captures_count() + 2
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        if c.get(offset).is_some(){
+            Ok(Ok(T::from_captures(c, offset + 1)?))
+        }else{
+            Ok(Err(E::from_captures(c, offset + T::captures_count() + 2)?))
+        }
+    }
+}
+
+impl<T: Reformation + 'static> Reformation for Range<T>{
+    fn regex_str()->&'static str{
+        generic_regex_str::<Self, _>(|| format!(r"{0}\.\.{0}", T::regex_str()))
+    }
+
+    fn captures_count()->usize{
+        2 * T::captures_count()
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let (start, offset) = T::from_captures_consuming(c, offset)?;
+        let end = T::from_captures(c, offset)?;
+        Ok(start..end)
+    }
+}
+
+impl<T: Reformation + 'static> Reformation for RangeFrom<T>{
+    fn regex_str()->&'static str{
+        generic_regex_str::<Self, _>(|| format!(r"{}\.\.", T::regex_str()))
+    }
+
+    fn captures_count()->usize{
+        T::captures_count()
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(T::from_captures(c, offset)?..)
+    }
+}
+
+impl<T: Reformation + 'static> Reformation for RangeTo<T>{
+    fn regex_str()->&'static str{
+        generic_regex_str::<Self, _>(|| format!(r"\.\.{}", T::regex_str()))
+    }
+
+    fn captures_count()->usize{
+        T::captures_count()
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(..T::from_captures(c, offset)?)
+    }
+}
+
+/// Lower bound of interval in mathematical notation: `[3` is `Included(3)`, `(3` is
+/// `Excluded(3)`, and `(`, `(-inf` or `(-∞` is `Unbounded`. Infinite bound cannot be
+/// included, so `[-inf` is rejected.
+///
+/// ```
+/// use reformation::Reformation;
+/// use std::ops::Bound;
+///
+/// #[derive(Reformation, Debug)]
+/// #[reformation(r"{start}, {end}\]")]
+/// struct Interval{
+///     start: Bound<f64>,
+///     end: f64,
+/// }
+///
+/// fn main(){
+///     let i: Interval = "(0.5, 1]".parse().unwrap();
+///     assert_eq!(i.start, Bound::Excluded(0.5));
+///     let i: Interval = "(-inf, 1]".parse().unwrap();
+///     assert_eq!(i.start, Bound::Unbounded);
+/// }
+/// ```
+impl<T: Reformation + 'static> Reformation for Bound<T>{
+    fn regex_str()->&'static str{
+        generic_regex_str::<Self, _>(|| format!(r"([\[\(])(?:(-?(?:inf|∞))|({}))?", T::regex_str()))
+    }
+
+    fn captures_count()->usize{
+        T::captures_count() + 3
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let included = c.get(offset).map(|x| x.as_str()) == Some("[");
+        match (c.get(offset + 2).is_some(), included){
+            (true, true) => Ok(Bound::Included(T::from_captures(c, offset + 3)?)),
+            (true, false) => Ok(Bound::Excluded(T::from_captures(c, offset + 3)?)),
+            (false, false) => Ok(Bound::Unbounded),
+            (false, true) => Err("Included bound must be finite".into()),
+        }
+    }
+}
+
+macro_rules! group_impl_transparent{
+    ($($name: ident),*) => {
+        $(
+            /// Transparent wrapper, matching the same format as wrapped type
+            impl<T: Reformation> Reformation for $name<T>{
+                fn regex_str()->&'static str{
+                    T::regex_str()
+                }
+
+                fn captures_count()->usize{
+                    T::captures_count()
+                }
+
+                fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+                    Ok($name(T::from_captures(c, offset)?))
+                }
+
+                fn min_len()->usize{
+                    T::min_len()
+                }
+            }
+        )*
+    };
+}
+
+group_impl_transparent!{Reverse, Wrapping}
+
+/// Boxed value, matching the same format as `T`.
+///
+/// Regular expressions cannot describe arbitrary nesting (such as balanced
+/// parentheses), so type cannot contain itself. Nesting of fixed depth can
+/// be expressed with generic parameter:
+///
+/// ```
+/// use reformation::Reformation;
+///
+/// #[derive(Reformation, Debug)]
+/// #[reformation(r"\({left}, {right}\)")]
+/// struct Pair<T>{
+///     left: Box<T>,
+///     right: Box<T>,
+/// }
+///
+/// fn main(){
+///     let p: Pair<Pair<u32>> = "((1, 2), (3, 4))".parse().unwrap();
+///     assert_eq!((p.left.left, p.right.right), (Box::new(1), Box::new(4)));
+/// }
+/// ```
+impl<T: Reformation> Reformation for Box<T>{
+    fn regex_str()->&'static str{
+        T::regex_str()
+    }
+
+    fn captures_count()->usize{
+        T::captures_count()
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(Box::new(T::from_captures(c, offset)?))
+    }
+
+    fn min_len()->usize{
+        T::min_len()
+    }
+}
+
+/// Duration as number with unit: `ns`, `us` (`µs`), `ms`, `s`, `m`, `h` or `d`.
+/// Number can be fractional: `1.5s`, `0.25h`.
+impl Reformation for Duration{
+    fn regex_str()->&'static str{
+        concat!("(", sign!(), r"?(?:\d+(?:\.\d*)?|\.\d+))\s*(ns|us|µs|ms|s|m|h|d)")
+    }
+
+    fn captures_count()->usize{
+        2
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let value = c.get(offset).unwrap().as_str();
+        let unit = c.get(offset + 1).unwrap().as_str();
+        if value.starts_with('-'){
+            return Err(format!("Duration cannot be negative: {}{}", value, unit).into());
+        }
+        let (nanos, secs) = match unit{
+            "ns" => (1, 0),
+            "us" | "µs" => (1_000, 0),
+            "ms" => (1_000_000, 0),
+            "s" => (0, 1),
+            "m" => (0, 60),
+            "h" => (0, 3600),
+            _ => (0, 86400),
+        };
+        let overflow = || format!("Duration {}{} is too large", value, unit);
+        if let Ok(value) = value.parse::<u64>(){
+            let res = if secs > 0{
+                Duration::from_secs(value.checked_mul(secs).ok_or_else(overflow)?)
+            }else{
+                Duration::from_nanos(value.checked_mul(nanos).ok_or_else(overflow)?)
+            };
+            return Ok(res);
+        }
+        let value: f64 = value.parse()?;
+        let secs = if secs > 0{
+            value * secs as f64
+        }else{
+            value * nanos as f64 / 1e9
+        };
+        Duration::try_from_secs_f64(secs).map_err(|_| overflow().into())
+    }
+}
+
+/// IPv4 address in dotted decimal notation: `127.0.0.1`.
+/// Octets with leading zeros are rejected, unless field has
+/// `#[reformation(allow_leading_zeros)]` attribute.
+impl Reformation for Ipv4Addr{
+    fn regex_str()->&'static str{
+        r"(\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3})"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(c.get(offset).unwrap().as_str().parse()?)
+    }
+}
+
+/// IPv6 address in colon separated hexadecimal notation, possibly compressed
+/// or with embedded IPv4 address: `fe80::1`, `::ffff:10.0.0.1`.
+impl Reformation for Ipv6Addr{
+    fn regex_str()->&'static str{
+        r"((?:[0-9a-fA-F]{0,4}:){2,7}(?:[0-9a-fA-F]{1,4}|\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3})?)"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(c.get(offset).unwrap().as_str().parse()?)
+    }
+}
+
+macro_rules! group_impl_parse_socket{
+    ($($name: ty: $re: expr, $doc: expr;)*) => {
+        $(
+            #[doc = $doc]
+            impl Reformation for $name{
+                fn regex_str()->&'static str{
+                    $re
+                }
+
+                fn captures_count()->usize{
+                    1
+                }
+
+                fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+                    Ok(c.get(offset).unwrap().as_str().parse()?)
+                }
+            }
+        )*
+    };
+}
+
+group_impl_parse_socket!{
+    SocketAddrV4: r"(\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}:\d{1,5})",
+        "IPv4 address with port: `127.0.0.1:8080`";
+    SocketAddrV6: r"(\[[0-9a-fA-F:\.]+(?:%\d+)?\]:\d{1,5})",
+        "IPv6 address in brackets with port: `[::1]:8080`";
+    SocketAddr: r"(\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}:\d{1,5}|\[[0-9a-fA-F:\.]+(?:%\d+)?\]:\d{1,5})",
+        "Either IPv4 or IPv6 address with port";
+}
+
+
+
+macro_rules! group_impl_tuple{
+    ($(($($name: ident $value: ident),*))*) => {
+        $(
+            /// Tuple in rust syntax: `(a, b)`
+            impl<$($name: Reformation + 'static),*> Reformation for ($($name,)*){
+                fn regex_str()->&'static str{
+                    generic_regex_str::<Self, _>(||{
+                        let items: &[&str] = &[$($name::regex_str()),*];
+                        format!(r"\({}\)", items.join(r",\s*"))
+                    })
+                }
+
+                fn captures_count()->usize{
+                    0 $(+ $name::captures_count())*
+                }
+
+                fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+                    $(
+                        let ($value, offset) = $name::from_captures_consuming(c, offset)?;
+                    )*
+                    let _ = offset;
+                    Ok(($($value,)*))
+                }
+            }
+        )*
+    };
+}
+
+group_impl_tuple!{
+    (A a, B b)
+    (A a, B b, C c)
+    (A a, B b, C c, D d)
+    (A a, B b, C c, D d, E e)
+    (A a, B b, C c, D d, E e, F f)
+    (A a, B b, C c, D d, E e, F f, G g)
+    (A a, B b, C c, D d, E e, F f, G g, H h)
+    (A a, B b, C c, D d, E e, F f, G g, H h, I i)
+    (A a, B b, C c, D d, E e, F f, G g, H h, I i, J j)
+    (A a, B b, C c, D d, E e, F f, G g, H h, I i, J j, K k)
+    (A a, B b, C c, D d, E e, F f, G g, H h, I i, J j, K k, L l)
+}
+
+
+/// Integer types, which can be parsed in arbitrary radix.
+/// Used by `#[reformation(radix = N)]` field attribute.
+pub trait FromStrRadix: Sized{
+    fn from_str_radix(src: &str, radix: u32)->Result<Self, Box<dyn Error>>;
+}
+
+macro_rules! group_impl_from_str_radix{
+    ($($name: ty),*) => {
+        $(
+            impl FromStrRadix for $name{
+                fn from_str_radix(src: &str, radix: u32)->Result<Self, Box<dyn Error>>{
+                    Ok(<$name>::from_str_radix(src, radix)?)
+                }
+            }
+        )*
+    };
+    ($($name: ty: $base: ty),*) => {
+        $(
+            impl FromStrRadix for $name{
+                fn from_str_radix(src: &str, radix: u32)->Result<Self, Box<dyn Error>>{
+                    let value = <$base>::from_str_radix(src, radix)?;
+                    // Obtain std error for zero value, since it cannot be constructed directly
+                    <$name>::new(value).ok_or_else(|| "0".parse::<$name>().unwrap_err().into())
+                }
+            }
+        )*
+    };
+}
+
+group_impl_from_str_radix!{u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize}
+group_impl_from_str_radix!{
+    NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64, NonZeroU128: u128, NonZeroUsize: usize,
+    NonZeroI8: i8, NonZeroI16: i16, NonZeroI32: i32, NonZeroI64: i64, NonZeroI128: i128, NonZeroIsize: isize
+}
+
+/// Types, which can be compared with bounds of `#[reformation(range = "..")]` field attribute.
+///
+/// Bounds are written as floating point numbers, so only float types implement it:
+/// converting them to integer could silently truncate or round bound.
+#[diagnostic::on_unimplemented(
+    message = "`range` requires float field, but `{Self}` is not supported",
+    label = "field bounded by `range` attribute",
+)]
+pub trait RangeBound{
+    fn range_bound(bound: f64)->Self;
+}
+
+macro_rules! group_impl_range_bound{
+    ($($name: ty),*) => {
+        $(
+            impl RangeBound for $name{
+                fn range_bound(bound: f64)->Self{
+                    bound as $name
+                }
+            }
+        )*
+    };
+}
+
+group_impl_range_bound!{f32, f64}
+
+/// Parse integer in given radix. Prefixes `0x`, `0o` and `0b` are allowed
+/// for radix 16, 8 and 2 respectively.
+///
+/// ```
+/// assert_eq!(reformation::parse_radix::<i32>("-0x1f", 16).unwrap(), -31);
+/// assert_eq!(reformation::parse_radix::<u8>("0b101", 2).unwrap(), 5);
+/// ```
+pub fn parse_radix<T: FromStrRadix>(src: &str, radix: u32)->Result<T, Box<dyn Error>>{
+    let (sign, digits) = match src.chars().next(){
+        Some(c) if c == '+' || c == '-' => src.split_at(1),
+        _ => ("", src),
+    };
+    let prefix = match radix{
+        16 => "0x",
+        8 => "0o",
+        2 => "0b",
+        _ => "",
+    };
+    let digits = if !prefix.is_empty() && digits.to_lowercase().starts_with(prefix){
+        &digits[2..]
+    }else{
+        digits
+    };
+    T::from_str_radix(&format!("{}{}", sign, digits), radix)
+}
+
+/// Parse unsigned integer in given radix as bits of signed integer `T`
+/// in two's complement representation: `0xff` is `-1i8`.
+/// Value must fit into width of `T`.
+///
+/// ```
+/// assert_eq!(reformation::parse_twos_complement::<i8>("0xff", 16).unwrap(), -1);
+/// assert_eq!(reformation::parse_twos_complement::<i8>("7f", 16).unwrap(), 127);
+/// assert!(reformation::parse_twos_complement::<i8>("0x100", 16).is_err());
+/// ```
+pub fn parse_twos_complement<T>(src: &str, radix: u32)->Result<T, Box<dyn Error>>
+    where T: TryFrom<i128>,
+          T::Error: Error + 'static,
+{
+    let value: u128 = parse_radix(src, radix)?;
+    let bits = 8 * std::mem::size_of::<T>() as u32;
+    if bits < 128 && value >> bits != 0{
+        return Err(format!("Value {} does not fit into {} bits", src, bits).into());
+    }
+    // arithmetic shift extends sign bit of `T`
+    let shift = 128 - bits;
+    Ok(T::try_from(((value << shift) as i128) >> shift)?)
+}
+
+/// Byte offset in input, where matching of regular expression most likely failed:
+/// furthest end of match of any prefix of regular expression, with open groups closed.
+///
+/// ```
+/// assert_eq!(reformation::failure_position(r"(\d+)-(\d+)", "12-x"), 3);
+/// ```
+pub fn failure_position(re_str: &str, input: &str)->usize{
+    let mut position = 0;
+    let mut depth = 0;
+    let mut class_depth = 0;
+    let mut escaped = false;
+    for (i, c) in re_str.char_indices(){
+        match c{
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' => class_depth += 1,
+            ']' if class_depth > 0 => class_depth -= 1,
+            '(' if class_depth == 0 => depth += 1,
+            ')' if class_depth == 0 => depth -= 1,
+            _ => {},
+        }
+        if escaped || class_depth > 0{
+            continue;
+        }
+        // close groups left open by prefix, prefixes which still do not compile are skipped
+        let prefix = format!("{}{}", &re_str[..i + c.len_utf8()], ")".repeat(depth));
+        if let Ok(re) = Regex::new(&prefix){
+            if let Some(m) = re.find(input){
+                position = position.max(m.end());
+            }
+        }
+    }
+    position
+}
+
+/// Line and column of byte offset in input, both starting from 1.
+/// Column is counted in characters. Offset past the end of input is clamped
+/// to its end, and offset inside of character to the start of this character.
+///
+/// ```
+/// assert_eq!(reformation::line_column("ab\ncd\nef", 7), (3, 2));
+/// assert_eq!(reformation::line_column("ab\ncd", 100), (2, 3));
+/// assert_eq!(reformation::line_column("aµb", 2), (1, 2));
+/// ```
+pub fn line_column(input: &str, position: usize)->(usize, usize){
+    let mut position = position.min(input.len());
+    while !input.is_char_boundary(position){
+        position -= 1;
+    }
+    let before = &input[..position];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Remove leading zeros of every number in string, keeping single zero.
+///
+/// Number with single `.` is decimal, so zeros of its fractional part are kept,
+/// while numbers separated by several dots are stripped individually.
+///
+/// ```
+/// assert_eq!(reformation::strip_leading_zeros("010.000.001.001"), "10.0.1.1");
+/// assert_eq!(reformation::strip_leading_zeros("-007.05"), "-7.05");
+/// assert_eq!(reformation::strip_leading_zeros(".05e007"), ".05e7");
+/// ```
+pub fn strip_leading_zeros(s: &str)->String{
+    let mut res = String::with_capacity(s.len());
+    let is_number = |c: char| c.is_ascii_digit() || c == '.';
+    let mut rest = s;
+    while let Some(start) = rest.find(is_number){
+        res.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c| !is_number(c)).unwrap_or(rest.len());
+        let number = &rest[..end];
+        let dotted = number.matches('.').count() > 1;
+        for (i, part) in number.split('.').enumerate(){
+            if i > 0{
+                res.push('.');
+            }
+            let stripped = part.trim_start_matches('0');
+            if (i > 0 && !dotted) || part.is_empty(){
+                res.push_str(part);
+            }else if stripped.is_empty(){
+                res.push('0');
+            }else{
+                res.push_str(stripped);
+            }
+        }
+        rest = &rest[end..];
+    }
+    res.push_str(rest);
+    res
+}
+
+/// Parse decimal number as integer scaled by `10^scale`: with scale 2 `3.14` is `314`
+/// and `3.1` is `310`. Number with more fractional digits than `scale` is rejected
+/// rather than rounded or truncated, so no precision is lost silently.
+///
+/// ```
+/// assert_eq!(reformation::parse_scaled::<i64>("-3.14", 2).unwrap(), -314);
+/// assert_eq!(reformation::parse_scaled::<u32>("7", 3).unwrap(), 7000);
+/// assert!(reformation::parse_scaled::<i64>("3.145", 2).is_err());
+/// ```
+pub fn parse_scaled<T>(src: &str, scale: usize)->Result<T, Box<dyn Error>>
+    where T: FromStr,
+          T::Err: Error + 'static,
+{
+    let (integer, fraction) = match src.find('.'){
+        Some(i) => (&src[..i], &src[i + 1..]),
+        None => (src, ""),
+    };
+    if fraction.len() > scale{
+        return Err(format!("Value {} has more than {} fractional digits", src, scale).into());
+    }
+    // `.5` and `5.` are accepted as well as `0.5` and `5.0`
+    let integer = match integer{
+        "" | "+" | "-" => format!("{}0", integer),
+        _ => integer.to_string(),
+    };
+    Ok(format!("{}{:0<width$}", integer, fraction, width = scale).parse()?)
+}
+
+/// Result of validation predicate used by `#[reformation(validate = ...)]` field attribute.
+pub trait Validation{
+    /// `Err` with description of failure if value is invalid
+    fn into_result(self)->Result<(), Box<dyn Error>>;
+}
+
+impl Validation for bool{
+    fn into_result(self)->Result<(), Box<dyn Error>>{
+        if self{
+            Ok(())
+        }else{
+            Err("predicate returned false".into())
+        }
+    }
+}
+
+impl<E: Into<Box<dyn Error>>> Validation for Result<(), E>{
+    fn into_result(self)->Result<(), Box<dyn Error>>{
+        self.map_err(Into::into)
+    }
+}
+
+/// Parse value of named capture group, for hand written `Reformation` implementations.
+///
+/// ```
+/// use reformation::Regex;
+///
+/// let re = Regex::new(r"(?P<x>\d+)-(?P<y>\d+)").unwrap();
+/// let c = re.captures("3-4").unwrap();
+/// let x: u8 = reformation::named(&c, "x").unwrap();
+/// assert_eq!(x, 3);
+/// ```
+pub fn named<'a, T>(c: &Captures<'a>, name: &str)->Result<T, Box<dyn Error>>
+    where T: FromStr,
+          T::Err: Error + 'static,
+{
+    let value = c.name(name)
+        .ok_or_else(|| format!("Named group `{}` did not participate in match", name))?;
+    Ok(value.as_str().parse()?)
+}
+
+/// Check that regular expression of `T` compiles, and number of its capture
+/// groups equals `T::captures_count()`. Mismatch shifts capture groups of
+/// all fields following `T`, so call it from tests of types with hand written
+/// `Reformation` implementations or field regular expressions.
+///
+/// ```
+/// use reformation::Reformation;
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{x}, {y}")]
+/// struct Point{
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// assert_eq!(reformation::audit::<Point>(), Ok(()));
+/// ```
+pub fn audit<T: Reformation>()->Result<(), String>{
+    let re = Regex::new(T::regex_str())
+        .map_err(|e| format!("Regular expression r{:?} does not compile: {}", T::regex_str(), e))?;
+    let groups = re.captures_len() - 1;
+    if groups == T::captures_count(){
+        Ok(())
+    }else{
+        Err(format!(
+            "Regular expression r{:?} has {} capture groups, but `captures_count` is {}",
+            T::regex_str(), groups, T::captures_count()
+        ))
+    }
+}
+
+/// Convert all capture groups of regular expression into non-capturing.
+///
+/// Useful to embed regular expression of another type, when its value
+/// is extracted from whole match rather than from capture groups.
+///
+/// ```
+/// assert_eq!(reformation::non_capturing(r"(\d+)\((?P<x>[(]\d)"), r"(?:\d+)\((?:[(]\d)");
+/// ```
+pub fn non_capturing(re: &str)->String{
+    let mut res = String::with_capacity(re.len());
+    let mut chars = re.chars().peekable();
+    let mut class_depth = 0;
+    while let Some(c) = chars.next(){
+        match c{
+            '\\' => {
+                res.push(c);
+                if let Some(c) = chars.next(){
+                    res.push(c);
+                }
+            },
+            '[' => {
+                class_depth += 1;
+                res.push(c);
+                // `]` right after opening bracket is literal
+                if chars.peek() == Some(&'^'){
+                    res.push(chars.next().unwrap());
+                }
+                if chars.peek() == Some(&']'){
+                    res.push(chars.next().unwrap());
+                }
+            },
+            ']' if class_depth > 0 => {
+                class_depth -= 1;
+                res.push(c);
+            },
+            '(' if class_depth == 0 => {
+                if chars.peek() != Some(&'?'){
+                    res.push_str("(?:");
+                    continue;
+                }
+                chars.next();
+                let named = match chars.peek(){
+                    Some('P') => {
+                        chars.next();
+                        true
+                    },
+                    Some('<') => true,
+                    _ => false,
+                };
+                if named{
+                    for c in &mut chars{
+                        if c == '>'{
+                            break;
+                        }
+                    }
+                    res.push_str("(?:");
+                }else{
+                    res.push_str("(?");
+                }
+            },
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+/// Make repetitions `*`, `+` and `{n,m}` of regular expression lazy.
+/// Optional items `?` are left greedy, since making them lazy changes
+/// what is matched rather than how.
+///
+/// ```
+/// assert_eq!(reformation::lazy_quantifiers(r"(\d+)\*([a+]*)\p{L}{2,}?"), r"(\d+?)\*([a+]*?)\p{L}{2,}?");
+/// ```
+pub fn lazy_quantifiers(re: &str)->String{
+    let mut res = String::with_capacity(re.len());
+    let mut chars = re.chars().peekable();
+    let mut class_depth = 0;
+    while let Some(c) = chars.next(){
+        res.push(c);
+        match c{
+            '\\' => {
+                if let Some(c) = chars.next(){
+                    res.push(c);
+                    // `\p{L}`, `\x{7F}`: braces are not repetition
+                    if c.is_ascii_alphabetic() && chars.peek() == Some(&'{'){
+                        for c in &mut chars{
+                            res.push(c);
+                            if c == '}'{
+                                break;
+                            }
+                        }
+                    }
+                }
+            },
+            '[' => {
+                class_depth += 1;
+                if chars.peek() == Some(&'^'){
+                    res.push(chars.next().unwrap());
+                }
+                if chars.peek() == Some(&']'){
+                    res.push(chars.next().unwrap());
+                }
+            },
+            ']' if class_depth > 0 => {
+                class_depth -= 1;
+            },
+            '*' | '+' | '}' if class_depth == 0 && chars.peek() != Some(&'?') => {
+                res.push('?');
+            },
+            _ => {},
+        }
+    }
+    res
+}
+
+/// Disable Unicode mode of regular expression: classes `\d`, `\w`, `\s` and
+/// word boundaries become ASCII only, making matching faster.
+/// Parts, which cannot be expressed without Unicode mode, keep it: any character `.`,
+/// negated classes `\D`, `\W`, `\S`, `[^...]` (otherwise they could match part of
+/// character), Unicode classes `\p{...}`, and non-ASCII characters, either literal
+/// or escaped, along with classes containing them.
+///
+/// ```
+/// assert_eq!(reformation::ascii_regex(r"(\w+)=(.*)"), r"(?-u:(\w+)=((?u:.)*))");
+/// assert_eq!(reformation::ascii_regex(r"([\+\-±]?)(\S+)"), r"(?-u:((?u:[\+\-±])?)((?u:\S)+))");
+/// ```
+pub fn ascii_regex(re: &str)->String{
+    let mut res = String::with_capacity(re.len() + 8);
+    res.push_str("(?-u:");
+    let mut chars = re.chars().peekable();
+    while let Some(c) = chars.next(){
+        match c{
+            '\\' => {
+                let (escape, unicode) = ascii_regex_escape(&mut chars);
+                if unicode{
+                    res.push_str(&format!("(?u:{})", escape));
+                }else{
+                    res.push_str(&escape);
+                }
+            },
+            '.' => res.push_str("(?u:.)"),
+            '[' => {
+                let mut class = String::from("[");
+                let mut depth = 1;
+                let mut unicode = chars.peek() == Some(&'^');
+                if chars.peek() == Some(&'^'){
+                    class.push(chars.next().unwrap());
+                }
+                if chars.peek() == Some(&']'){
+                    class.push(chars.next().unwrap());
+                }
+                while let Some(c) = chars.next(){
+                    match c{
+                        '\\' => {
+                            let (escape, escape_unicode) = ascii_regex_escape(&mut chars);
+                            unicode |= escape_unicode;
+                            class.push_str(&escape);
+                            continue;
+                        },
+                        '[' => depth += 1,
+                        ']' => depth -= 1,
+                        c if !c.is_ascii() => unicode = true,
+                        _ => {},
+                    }
+                    class.push(c);
+                    if depth == 0{
+                        break;
+                    }
+                }
+                if unicode{
+                    res.push_str(&format!("(?u:{})", class));
+                }else{
+                    res.push_str(&class);
+                }
+            },
+            c if !c.is_ascii() => res.push_str(&format!("(?u:{})", c)),
+            _ => res.push(c),
+        }
+    }
+    res.push(')');
+    res
+}
+
+/// Escape sequence following `\`, and whether it requires Unicode mode
+fn ascii_regex_escape(chars: &mut std::iter::Peekable<std::str::Chars>)->(String, bool){
+    let mut escape = String::from("\\");
+    let c = match chars.next(){
+        Some(c) => c,
+        None => return (escape, false),
+    };
+    escape.push(c);
+    match c{
+        'D' | 'W' | 'S' | 'B' => (escape, true),
+        'p' | 'P' => {
+            if chars.peek() == Some(&'{'){
+                for c in chars.by_ref(){
+                    escape.push(c);
+                    if c == '}'{
+                        break;
+                    }
+                }
+            }else if let Some(c) = chars.next(){
+                escape.push(c);
+            }
+            (escape, true)
+        },
+        'x' | 'u' | 'U' => {
+            let mut digits = String::new();
+            if chars.peek() == Some(&'{'){
+                escape.push(chars.next().unwrap());
+                for c in chars.by_ref(){
+                    escape.push(c);
+                    if c == '}'{
+                        break;
+                    }
+                    digits.push(c);
+                }
+            }else{
+                let len = match c{
+                    'x' => 2,
+                    'u' => 4,
+                    _ => 8,
+                };
+                for _ in 0..len{
+                    if let Some(c) = chars.next(){
+                        escape.push(c);
+                        digits.push(c);
+                    }
+                }
+            }
+            let unicode = u32::from_str_radix(&digits, 16).map(|x| x > 0x7f).unwrap_or(true);
+            (escape, unicode)
+        },
+        c => (escape, !c.is_ascii()),
+    }
+}
+
 
 /// Creates function for parsing tuple of values from
 /// strings corresponding to given template.
@@ -139,11 +1738,12 @@ group_impl_parse_primitive!{r"(.*)", String}
 /// where:
 /// + function_name -- Name of function to be created.
 /// + re -- Format string for matching arguments. Format string is regular
-///     expression, preprocessed by macro, and rules simular to regexprs
-///     applies to it. In order to macro work properly usage of capture
-///     groups should be avoided. Non capturing `(:?groups)` are fine.
-/// + types.. -- sequence of types expected as function output. Each type must
-///     implement trait ```ParsePrimitive```. Default implementors:
+///   expression, preprocessed by macro, and rules simular to regexprs
+///   applies to it. In order to macro work properly usage of capture
+///   groups should be avoided. Non capturing `(:?groups)` are fine.
+/// + types.. -- sequence of types expected as function output. Number of types
+///   is not limited (unlike tuples implementing `Reformation`). Each type must
+///   implement trait ```Reformation```. Default implementors:
 ///     + unsigned integers: u8, u16, u32, u64, u128, usize
 ///     + signed integers: i8, i16, i16, i64, i128, isize
 ///     + floating point numbers: f32, f64,
@@ -197,19 +1797,17 @@ group_impl_parse_primitive!{r"(.*)", String}
 #[macro_export]
 macro_rules! create_parse_fn{
     ($name: ident, $re: expr, $($res: ty),*) => {
-        fn $name(s: &str)->Result<($($res),*), Box<std::error::Error>>{
-            create_parse_fn!(@body s, $re, $($res),*)
+        fn $name(s: &str)->Result<($($res),*), Box<dyn std::error::Error>>{
+            $crate::create_parse_fn!(@body s, $re, $($res),*)
         }
     };
     (@body $str: expr, $re: expr, $($res: ty),*) => {
         {
-            type OkType = ($($res),*);
-
             // create regex automation with captures for each argument
             ::reformation::lazy_static!{
                 static ref REGEX: ::reformation::Regex = {
                     let re_str = format!($re, $(<$res as ::reformation::Reformation>::regex_str()),*);
-                    ::reformation::Regex::new(&re_str).unwrap()
+                    ::reformation::cached_regex(&re_str).unwrap()
                 };
             }
 
@@ -219,13 +1817,121 @@ macro_rules! create_parse_fn{
                     request: $str.to_string()
                 }
             })?;
-            let mut i=0;
-            Ok((
-                $({
-                    i += 1;
-                    captures.get(i).unwrap().as_str().parse::<$res>()?
-                }),*
-            ))
+            $crate::create_parse_fn!(@captures captures, 1, $($res),*)
+        }
+    };
+    // values are parsed one by one rather than by `Reformation` of tuple,
+    // so number of types is not limited by tuple implementations
+    (@captures $captures: expr, $offset: expr, $($res: ty),*) => {
+        {
+            let mut offset = $offset;
+            let values = ($({
+                let (value, next) = <$res as $crate::Reformation>::from_captures_consuming(&$captures, offset)?;
+                offset = next;
+                value
+            }),*);
+            let _ = offset;
+            Ok(values)
+        }
+    };
+}
+
+/// Creates newtype over tuple of values, implementing `Reformation` with the
+/// same format string as `create_parse_fn!`, so it can be used as field of
+/// derived struct.
+///
+/// usage: `create_parse_type!{TypeName, re, types..}`
+///
+/// Unlike format of `create_parse_fn!`, format of embedded type should not be
+/// anchored with `^` and `$`.
+///
+/// ```
+/// use reformation::{Reformation, create_parse_type};
+///
+/// create_parse_type!{
+///     /// Size in format `640x480`
+///     #[derive(Debug, PartialEq)]
+///     pub Size, r"{}x{}", u32, u32
+/// }
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{name}: {size}")]
+/// struct Screen{
+///     name: String,
+///     size: Size,
+/// }
+///
+/// fn main(){
+///     let screen: Screen = "vga: 640x480".parse().unwrap();
+///     assert_eq!(screen.name, "vga");
+///     assert_eq!(screen.size, Size((640, 480)));
+/// }
+/// ```
+#[macro_export]
+macro_rules! create_parse_type{
+    ($(#[$meta: meta])* $vis: vis $name: ident, $re: expr, $($res: ty),*) => {
+        $(#[$meta])*
+        $vis struct $name(pub ($($res),*));
+
+        impl $crate::Reformation for $name{
+            fn regex_str()->&'static str{
+                $crate::lazy_static!{
+                    static ref STR: String = format!($re, $(<$res as $crate::Reformation>::regex_str()),*);
+                }
+                &STR
+            }
+
+            fn captures_count()->usize{
+                0 $(+ <$res as $crate::Reformation>::captures_count())*
+            }
+
+            fn from_captures(c: &$crate::Captures, offset: usize)->Result<Self, Box<dyn ::std::error::Error>>{
+                let values: Result<_, Box<dyn ::std::error::Error>> = $crate::create_parse_fn!(@captures c, offset, $($res),*);
+                values.map($name)
+            }
+        }
+    };
+}
+
+/// Define struct with named fields and derive `Reformation` for it, with format
+/// string (and other struct arguments) given after the struct.
+///
+/// usage: `reformation_struct!{struct Name{fields..} = r"format", args..}`
+///
+/// ```
+/// use reformation::reformation_struct;
+///
+/// reformation_struct!{
+///     /// Color in format `rgb(r, g, b)`
+///     #[derive(Debug, PartialEq)]
+///     pub struct Color{
+///         pub r: u8,
+///         pub g: u8,
+///         #[reformation(checked)]
+///         pub b: u8,
+///     } = r"rgb\({r},\s*{g},\s*{b}\)", canonical
+/// }
+///
+/// fn main(){
+///     let color: Color = "rgb(255,128, 0)".parse().unwrap();
+///     assert_eq!(color, Color{r: 255, g: 128, b: 0});
+///     assert_eq!(color.canonical(), "rgb(255,128,0)");
+///     assert!("rgb(255, 128)".parse::<Color>().is_err());
+/// }
+/// ```
+#[macro_export]
+macro_rules! reformation_struct{
+    (
+        $(#[$meta: meta])*
+        $vis: vis struct $name: ident{
+            $($(#[$field_meta: meta])* $field_vis: vis $field: ident: $ty: ty),* $(,)?
+        } = $($args: tt)+
+    ) => {
+        $(#[$meta])*
+        #[derive($crate::Reformation)]
+        #[reformation($($args)+)]
+        $vis struct $name{
+            $($(#[$field_meta])* $field_vis $field: $ty),*
         }
     };
 }
@@ -251,13 +1957,16 @@ mod tests{
         assert!(check_float_capture(&re, ".34e-2"));
         assert!(check_float_capture(&re, "-0.34e-2"));
         assert!(check_float_capture(&re, "5e-2"));
-        assert!(check_float_capture(&re, "5.e-2")); // should this pass?
+        // accepted, since `str::parse` accepts it too. Use `strict_float`
+        // field attribute to reject it
+        assert!(check_float_capture(&re, "5.e-2"));
 
         // negative
         assert!(! re.is_match("5.."));
         assert!(! re.is_match("."));
         assert!(! re.is_match("--4."));
         assert!(! re.is_match("-.0"));
+        assert!(! re.is_match("5x3"));
     }
 
     fn check_float_capture(r: &regex::Regex, s: &str)->bool{
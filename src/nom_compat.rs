@@ -0,0 +1,41 @@
+use crate::{Reformation, generic_regex};
+use nom::error::{Error, ErrorKind};
+use nom::IResult;
+use std::marker::PhantomData;
+
+/// Key of anchored regular expression of `T` among regexes of generic types
+struct Anchored<T>(PhantomData<T>);
+
+/// Parse value of type `T` from the start of input, returning rest of input along
+/// with the value. Function has signature of `nom` parser, so it can be used in
+/// combinators of `nom`.
+///
+/// Input, which does not match regular expression of `T`, results in error of
+/// kind `RegexpCapture`, and matched value, which cannot be parsed, in error of
+/// kind `MapRes`. Both are recoverable, so alternatives can be tried.
+///
+/// Anchored regular expression is compiled once per type, so `T` must be `'static`.
+///
+/// Requires feature `nom`.
+///
+/// ```
+/// use reformation::Reformation;
+/// use nom::sequence::separated_pair;
+/// use nom::bytes::complete::tag;
+///
+/// fn main(){
+///     let mut pair = separated_pair(reformation::parse_nom::<u8>, tag(":"), reformation::parse_nom::<f32>);
+///     assert_eq!(pair("12:2.5 rest"), Ok((" rest", (12, 2.5))));
+///     assert!(pair("300:2.5").is_err());
+/// }
+/// ```
+pub fn parse_nom<T: Reformation + 'static>(input: &str)->IResult<&str, T>{
+    let re = generic_regex::<Anchored<T>, _>(|| format!(r"\A(?:{})", T::regex_str()))
+        .unwrap_or_else(|x| panic!("Cannot compile regex {:?}", x));
+    let captures = re.captures(input)
+        .ok_or_else(|| nom::Err::Error(Error::new(input, ErrorKind::RegexpCapture)))?;
+    let value = T::from_captures(&captures, 1)
+        .map_err(|_| nom::Err::Error(Error::new(input, ErrorKind::MapRes)))?;
+    let end = captures.get(0).unwrap().end();
+    Ok((&input[end..], value))
+}
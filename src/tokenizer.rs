@@ -0,0 +1,139 @@
+use crate::{Reformation, cached_regex};
+use std::error::Error;
+
+/// Sequential parser, consuming values of `Reformation` types one after another
+/// from the start of remaining input.
+///
+/// ```
+/// use reformation::Tokenizer;
+///
+/// let mut tokens = Tokenizer::new("12 -3.5");
+/// let a: u32 = tokens.next().unwrap();
+/// let b: f64 = tokens.next().unwrap();
+/// assert_eq!((a, b), (12, -3.5));
+/// assert!(tokens.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tokenizer<'a>{
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Tokenizer<'a>{
+    pub fn new(input: &'a str)->Self{
+        Self{input, position: 0}
+    }
+
+    /// Byte offset of not yet consumed input
+    pub fn position(&self)->usize{
+        self.position
+    }
+
+    /// Not yet consumed input
+    pub fn rest(&self)->&'a str{
+        &self.input[self.position..]
+    }
+
+    /// Is whole input consumed, except for trailing whitespace
+    pub fn is_empty(&self)->bool{
+        self.rest().trim_start().is_empty()
+    }
+
+    /// Skip whitespace at current position
+    pub fn skip_whitespace(&mut self){
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip whitespace, then consume value of type `T`, matched at current position.
+    /// On failure position is left unchanged.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: Reformation>(&mut self)->Result<T, Box<dyn Error>>{
+        let start = self.position;
+        self.skip_whitespace();
+        let res = self.next_raw();
+        if res.is_err(){
+            self.position = start;
+        }
+        res
+    }
+
+    /// Consume value of type `T`, matched exactly at current position.
+    /// On failure position is left unchanged.
+    pub fn next_raw<T: Reformation>(&mut self)->Result<T, Box<dyn Error>>{
+        let re = cached_regex(&format!(r"\A(?:{})", T::regex_str()))?;
+        let captures = re.captures(self.rest())
+            .ok_or_else(|| format!("Expected r{:?} at position {}", T::regex_str(), self.position))?;
+        let value = T::from_captures(&captures, 1)?;
+        self.position += captures.get(0).unwrap().end();
+        Ok(value)
+    }
+}
+
+
+/// Parse whitespace separated values of given types from whole input, using `Tokenizer`.
+///
+/// `scan!(input, T)` evaluates to `Result<T, Box<dyn Error>>`, and `scan!(input, A, B, ..)`
+/// to `Result<(A, B, ..), Box<dyn Error>>`. Error names type which failed to parse and
+/// its position. Input left after last value is error too.
+///
+/// ```
+/// use reformation::{Reformation, scan};
+///
+/// #[derive(Reformation, Debug, PartialEq)]
+/// #[reformation(r"{year}-{month}-{day}")]
+/// struct Date{
+///     year: u16,
+///     month: u8,
+///     day: u8,
+/// }
+///
+/// fn main(){
+///     let date = scan!("2018-12-22", Date).unwrap();
+///     assert_eq!(date, Date{year: 2018, month: 12, day: 22});
+///
+///     let (id, date) = scan!("7 2018-12-22", u32, Date).unwrap();
+///     assert_eq!((id, date.year), (7, 2018));
+///
+///     let err = scan!("7 2018/12/22", u32, Date).unwrap_err();
+///     assert!(err.to_string().starts_with("Cannot scan `Date` at position 1"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! scan{
+    ($input: expr, $ty: ty) => {
+        $crate::scan!($input, $ty,).map(|(value,)| value)
+    };
+    ($input: expr, $($ty: ty),+ $(,)?) => {
+        (||->Result<($($ty,)+), Box<dyn ::std::error::Error>>{
+            let mut tokens = $crate::Tokenizer::new($input);
+            let res = ($({
+                let position = tokens.position();
+                tokens.next::<$ty>().map_err(|e|{
+                    format!("Cannot scan `{}` at position {}: {}", stringify!($ty), position, e)
+                })?
+            },)+);
+            if !tokens.is_empty(){
+                return Err(format!("Unexpected input at position {}: {:?}", tokens.position(), tokens.rest()).into());
+            }
+            Ok(res)
+        })()
+    };
+}
+
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    #[test]
+    fn test_position_on_failure(){
+        let mut tokens = Tokenizer::new("  1 x");
+        assert_eq!(tokens.next::<u8>().unwrap(), 1);
+        assert_eq!(tokens.position(), 3);
+        assert!(tokens.next::<u8>().is_err());
+        assert_eq!(tokens.position(), 3);
+        assert!(tokens.next_raw::<u8>().is_err());
+        assert_eq!(tokens.rest(), " x");
+    }
+}
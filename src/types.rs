@@ -0,0 +1,774 @@
+//! Ready-made types for commonly used formats.
+
+use crate::{Reformation, RangeBound, Captures, cached_regex};
+use std::error::Error;
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::Neg;
+use std::time::Duration;
+
+/// Single ASCII character, stored as byte.
+///
+/// Useful for protocols with single byte command codes.
+/// Unlike `char` non ASCII characters are rejected.
+///
+/// ```
+/// use reformation::{Reformation, AsciiChar};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{code}{arg}")]
+/// struct Command{
+///     code: AsciiChar,
+///     arg: u32,
+/// }
+///
+/// fn main(){
+///     let cmd: Command = "M42".parse().unwrap();
+///     assert_eq!(cmd.code, AsciiChar(b'M'));
+///     assert_eq!(cmd.arg, 42);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AsciiChar(pub u8);
+
+impl Reformation for AsciiChar{
+    fn regex_str()->&'static str{
+        r"([\x00-\x7F])"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let byte = c.get(offset).unwrap().as_str().as_bytes()[0];
+        Ok(AsciiChar(byte))
+    }
+}
+
+
+/// Time of day in format `HH:MM:SS` with optional fraction of second,
+/// stored as duration since midnight.
+///
+/// ```
+/// use reformation::{Reformation, ClockTime};
+/// use std::time::Duration;
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"\[{time}\] {message}")]
+/// struct LogLine{
+///     time: ClockTime,
+///     message: String,
+/// }
+///
+/// fn main(){
+///     let line: LogLine = "[20:23:05.5] started".parse().unwrap();
+///     assert_eq!(line.time.0, Duration::from_millis(73385_500));
+///     assert_eq!(line.message, "started");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ClockTime(pub Duration);
+
+impl Reformation for ClockTime{
+    fn regex_str()->&'static str{
+        r"(\d{2}):(\d{2}):(\d{2})(?:\.(\d{1,9}))?"
+    }
+
+    fn captures_count()->usize{
+        4
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let part = |i: usize, max: u64|->Result<u64, Box<dyn Error>>{
+            let value = c.get(offset + i).unwrap().as_str().parse()?;
+            if value < max{
+                Ok(value)
+            }else{
+                Err(format!("Clock time component {} is out of range 0..{}", value, max).into())
+            }
+        };
+        let secs = part(0, 24)? * 3600 + part(1, 60)? * 60 + part(2, 60)?;
+        let nanos = match c.get(offset + 3){
+            Some(fraction) => {
+                let fraction = fraction.as_str();
+                fraction.parse::<u32>()? * 10u32.pow(9 - fraction.len() as u32)
+            },
+            None => 0,
+        };
+        Ok(ClockTime(Duration::new(secs, nanos)))
+    }
+}
+
+
+/// Sign written separately from magnitude: `+`, `-` (or unicode minus `−`) or `±`.
+///
+/// ```
+/// use reformation::{Reformation, Sign};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{sign}{error}")]
+/// struct Tolerance{
+///     sign: Sign,
+///     error: f64,
+/// }
+///
+/// fn main(){
+///     let t: Tolerance = "±3.2".parse().unwrap();
+///     assert_eq!(t.sign, Sign::PlusMinus);
+///     assert_eq!(t.error, 3.2);
+///     let t: Tolerance = "-0.5".parse().unwrap();
+///     assert_eq!(t.sign.apply(t.error), -0.5);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Sign{
+    Plus,
+    Minus,
+    PlusMinus,
+}
+
+impl Sign{
+    /// Apply sign to magnitude: negate it for `Minus`, and keep it as is otherwise.
+    pub fn apply<T: Neg<Output=T>>(self, value: T)->T{
+        match self{
+            Sign::Minus => -value,
+            _ => value,
+        }
+    }
+}
+
+impl Reformation for Sign{
+    fn regex_str()->&'static str{
+        r"([\+\-\u{2212}±])"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        match c.get(offset).unwrap().as_str(){
+            "+" => Ok(Sign::Plus),
+            "±" => Ok(Sign::PlusMinus),
+            _ => Ok(Sign::Minus),
+        }
+    }
+}
+
+
+/// Duration written as sequence of integer amounts with units, from largest
+/// unit to smallest: `1h30m15s`, `90m`, `2d12h`. Units are `d`, `h`, `m`, `s`,
+/// `ms`, `us` (`µs`) and `ns`. Each unit may appear only once, and units must be
+/// in descending order, so `30m1h` and `1m1m` are rejected.
+///
+/// ```
+/// use reformation::{Reformation, CompoundDuration};
+/// use std::time::Duration;
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"timeout={timeout}")]
+/// struct Config{
+///     timeout: CompoundDuration,
+/// }
+///
+/// fn main(){
+///     let config: Config = "timeout=1h30m".parse().unwrap();
+///     assert_eq!(config.timeout.0, Duration::from_secs(5400));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct CompoundDuration(pub Duration);
+
+impl Reformation for CompoundDuration{
+    fn regex_str()->&'static str{
+        r"((?:\d+(?:ns|us|µs|ms|s|m|h|d))+)"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        // units from largest to smallest: (name, seconds, nanoseconds)
+        const UNITS: &[(&str, u64, u64)] = &[
+            ("d", 86400, 0), ("h", 3600, 0), ("m", 60, 0), ("s", 1, 0),
+            ("ms", 0, 1_000_000), ("us", 0, 1_000), ("µs", 0, 1_000), ("ns", 0, 1),
+        ];
+        let s = c.get(offset).unwrap().as_str();
+        let re = cached_regex(r"(\d+)(ns|us|µs|ms|s|m|h|d)")?;
+        let overflow = || format!("Duration {} is too large", s);
+        let mut total = Duration::default();
+        let mut previous = None;
+        for part in re.captures_iter(s){
+            let unit = &part[2];
+            let rank = UNITS.iter().position(|(name, _, _)| *name == unit).unwrap();
+            // `us` and `µs` are the same unit
+            let rank = if unit == "µs"{ rank - 1 }else{ rank };
+            if previous.is_some_and(|previous| previous >= rank){
+                return Err(format!("Units of duration {} must be unique and in descending order", s).into());
+            }
+            previous = Some(rank);
+            let value: u64 = part[1].parse()?;
+            let (_, secs, nanos) = UNITS[rank];
+            let secs = value.checked_mul(secs).ok_or_else(overflow)?;
+            let nanos = value.checked_mul(nanos).ok_or_else(overflow)?;
+            let part = Duration::from_secs(secs).checked_add(Duration::from_nanos(nanos)).ok_or_else(overflow)?;
+            total = total.checked_add(part).ok_or_else(overflow)?;
+        }
+        Ok(CompoundDuration(total))
+    }
+}
+
+
+/// MAC address: six hex octets, separated by `:` or `-` (`01:23:45:67:89:ab`).
+/// All separators of single address must be the same.
+///
+/// ```
+/// use reformation::{Reformation, MacAddr};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{iface} link/ether {mac}")]
+/// struct Link{
+///     iface: String,
+///     mac: MacAddr,
+/// }
+///
+/// fn main(){
+///     let link: Link = "eth0 link/ether 01:23:45:67:89:ab".parse().unwrap();
+///     assert_eq!(link.iface, "eth0");
+///     assert_eq!(link.mac, MacAddr([0x01, 0x23, 0x45, 0x67, 0x89, 0xab]));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl Reformation for MacAddr{
+    fn regex_str()->&'static str{
+        r"((?:[0-9a-fA-F]{2}[:-]){5}[0-9a-fA-F]{2})"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let s = c.get(offset).unwrap().as_str();
+        let separator = &s[2..3];
+        let octets: Vec<_> = s.split(separator).collect();
+        if octets.len() != 6{
+            return Err(format!("MAC address {:?} mixes `:` and `-` separators", s).into());
+        }
+        let mut res = [0; 6];
+        for (x, octet) in res.iter_mut().zip(octets){
+            *x = u8::from_str_radix(octet, 16)?;
+        }
+        Ok(MacAddr(res))
+    }
+}
+
+
+/// Path split into components on `/`: `a/b/c` or `/usr/lib/`.
+///
+/// Leading slash marks path as absolute, trailing slash is ignored.
+/// Components are non empty and contain no whitespace, so `a//b` does not match.
+///
+/// ```
+/// use reformation::{Reformation, PathComponents};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"cd {path}")]
+/// struct Cd{
+///     path: PathComponents,
+/// }
+///
+/// fn main(){
+///     let cd: Cd = "cd /usr/lib/".parse().unwrap();
+///     assert!(cd.path.absolute);
+///     assert_eq!(cd.path.components, vec!["usr", "lib"]);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct PathComponents{
+    pub absolute: bool,
+    pub components: Vec<String>,
+}
+
+impl Reformation for PathComponents{
+    fn regex_str()->&'static str{
+        r"(/?(?:[^/\s]+/)*[^/\s]*)"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let s = c.get(offset).unwrap().as_str();
+        let absolute = s.starts_with('/');
+        let components = s.split('/')
+            .filter(|x| !x.is_empty())
+            .map(String::from)
+            .collect();
+        Ok(PathComponents{absolute, components})
+    }
+}
+
+
+/// IPv4 address in dotted decimal notation, together with its octets.
+///
+/// ```
+/// use reformation::{Reformation, Ip4Parsed};
+/// use std::net::Ipv4Addr;
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"inet {ip}")]
+/// struct Inet{
+///     ip: Ip4Parsed,
+/// }
+///
+/// fn main(){
+///     let inet: Inet = "inet 192.168.0.7".parse().unwrap();
+///     assert_eq!(inet.ip.addr, Ipv4Addr::new(192, 168, 0, 7));
+///     assert_eq!(inet.ip.octets, [192, 168, 0, 7]);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ip4Parsed{
+    pub addr: Ipv4Addr,
+    pub octets: [u8; 4],
+}
+
+impl From<Ipv4Addr> for Ip4Parsed{
+    fn from(addr: Ipv4Addr)->Self{
+        Ip4Parsed{addr, octets: addr.octets()}
+    }
+}
+
+impl Reformation for Ip4Parsed{
+    fn regex_str()->&'static str{
+        Ipv4Addr::regex_str()
+    }
+
+    fn captures_count()->usize{
+        Ipv4Addr::captures_count()
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ipv4Addr::from_captures(c, offset).map(Ip4Parsed::from)
+    }
+}
+
+/// IPv6 address, together with its 16-bit segments.
+///
+/// ```
+/// use reformation::{Reformation, Ip6Parsed};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"inet6 {ip}/{prefix}")]
+/// struct Inet6{
+///     ip: Ip6Parsed,
+///     prefix: u8,
+/// }
+///
+/// fn main(){
+///     let inet: Inet6 = "inet6 fe80::1/64".parse().unwrap();
+///     assert_eq!(inet.ip.segments, [0xfe80, 0, 0, 0, 0, 0, 0, 1]);
+///     assert_eq!(inet.prefix, 64);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ip6Parsed{
+    pub addr: Ipv6Addr,
+    pub segments: [u16; 8],
+}
+
+impl From<Ipv6Addr> for Ip6Parsed{
+    fn from(addr: Ipv6Addr)->Self{
+        Ip6Parsed{addr, segments: addr.segments()}
+    }
+}
+
+impl Reformation for Ip6Parsed{
+    fn regex_str()->&'static str{
+        Ipv6Addr::regex_str()
+    }
+
+    fn captures_count()->usize{
+        Ipv6Addr::captures_count()
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ipv6Addr::from_captures(c, offset).map(Ip6Parsed::from)
+    }
+}
+
+
+/// Number of bytes, written with unit suffix: `10MB`, `512KiB`, `64B`.
+///
+/// SI units are powers of 1000 (`KB`, `MB`, `GB`), while IEC units are powers
+/// of 1024 (`KiB`, `MiB`, `GiB`), so `1KB` is 1000 bytes and `1KiB` is 1024 bytes.
+/// Units are case sensitive, and unit `B` is required for plain bytes.
+///
+/// ```
+/// use reformation::{Reformation, ByteSize};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"cache_size = {size}")]
+/// struct Config{
+///     size: ByteSize,
+/// }
+///
+/// fn main(){
+///     let config: Config = "cache_size = 10MB".parse().unwrap();
+///     assert_eq!(config.size, ByteSize(10_000_000));
+///     let config: Config = "cache_size = 512KiB".parse().unwrap();
+///     assert_eq!(config.size, ByteSize(512 * 1024));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct ByteSize(pub u64);
+
+impl Reformation for ByteSize{
+    fn regex_str()->&'static str{
+        r"(\d+(?:[KMG]i?)?B)"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        const UNITS: &[(&str, u64)] = &[
+            ("B", 1),
+            ("KB", 1_000), ("MB", 1_000_000), ("GB", 1_000_000_000),
+            ("KiB", 1 << 10), ("MiB", 1 << 20), ("GiB", 1 << 30),
+        ];
+        let s = c.get(offset).unwrap().as_str();
+        let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (value, unit) = s.split_at(digits);
+        let (_, multiplier) = UNITS.iter()
+            .find(|(name, _)| *name == unit)
+            .ok_or_else(|| format!("Unknown unit of byte size {}", s))?;
+        let value: u64 = value.parse()?;
+        let bytes = value.checked_mul(*multiplier)
+            .ok_or_else(|| format!("Byte size {} is too large", s))?;
+        Ok(ByteSize(bytes))
+    }
+}
+
+/// Signed offset in format `±HH:MM[:SS]`, such as UTC offset `-05:30`,
+/// stored as number of seconds. Sign is optional for non negative offsets.
+///
+/// ```
+/// use reformation::{Reformation, SignedDuration};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"UTC{utc_offset}")]
+/// struct TimeZone{
+///     utc_offset: SignedDuration,
+/// }
+///
+/// fn main(){
+///     let tz: TimeZone = "UTC-05:30".parse().unwrap();
+///     assert_eq!(tz.utc_offset, SignedDuration(-(5 * 3600 + 30 * 60)));
+///     let tz: TimeZone = "UTC+01:00".parse().unwrap();
+///     assert_eq!(tz.utc_offset.0, 3600);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct SignedDuration(pub i64);
+
+impl Reformation for SignedDuration{
+    fn regex_str()->&'static str{
+        r"([\+\-\u{2212}]?)(\d{2,}):(\d{2})(?::(\d{2}))?"
+    }
+
+    fn captures_count()->usize{
+        4
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let part = |i: usize, max: i64|->Result<i64, Box<dyn Error>>{
+            let value = match c.get(offset + i){
+                Some(value) => value.as_str().parse()?,
+                None => 0,
+            };
+            if value < max{
+                Ok(value)
+            }else{
+                Err(format!("Offset component {} is out of range 0..{}", value, max).into())
+            }
+        };
+        let seconds = part(1, i64::MAX / 3600)? * 3600 + part(2, 60)? * 60 + part(3, 60)?;
+        match c.get(offset).unwrap().as_str(){
+            "" | "+" => Ok(SignedDuration(seconds)),
+            _ => Ok(SignedDuration(-seconds)),
+        }
+    }
+}
+
+
+/// Percentage written with `%` sign: `50%`, `12.5%`.
+///
+/// Value must be within `0..=100`. Other bounds can be set with `range` field
+/// attribute, which replaces the default one: `#[reformation(range = "0..=200")]`
+/// accepts `150%`.
+///
+/// ```
+/// use reformation::{Reformation, Percent};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{done} done, {growth} growth")]
+/// struct Progress{
+///     done: Percent,
+///     #[reformation(range = "-100..")]
+///     growth: Percent,
+/// }
+///
+/// fn main(){
+///     let p: Progress = "12.5% done, 300% growth".parse().unwrap();
+///     assert_eq!(p.done.fraction(), 0.125);
+///     assert_eq!(p.growth.percentage(), 300.0);
+///     assert!("120% done, 0% growth".parse::<Progress>().is_err());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Percent(pub f64);
+
+impl Percent{
+    /// Value in percents: `50.0` for `50%`
+    pub fn percentage(self)->f64{
+        self.0
+    }
+
+    /// Value as fraction of one: `0.5` for `50%`
+    pub fn fraction(self)->f64{
+        self.0 / 100.0
+    }
+}
+
+impl fmt::Display for Percent{
+    fn fmt(&self, f: &mut fmt::Formatter)->fmt::Result{
+        write!(f, "{}%", self.0)
+    }
+}
+
+impl Reformation for Percent{
+    fn regex_str()->&'static str{
+        concat!("(", sign!(), r"?(?:\d+(?:\.\d*)?|\.\d+))%")
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let value = Self::from_captures_unbounded(c, offset)?;
+        if (0.0..=100.0).contains(&value.0){
+            Ok(value)
+        }else{
+            Err(format!("Percentage {} is out of range 0..=100", value).into())
+        }
+    }
+
+    fn from_captures_unbounded(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(Percent(c.get(offset).unwrap().as_str().parse()?))
+    }
+}
+
+/// Bounds of `range` attribute are given in percents
+impl RangeBound for Percent{
+    fn range_bound(bound: f64)->Self{
+        Percent(bound)
+    }
+}
+
+
+/// Three valued logical value: `true`, `false` or `unknown`.
+///
+/// Types with other tokens can be declared with `tri_state!` macro.
+///
+/// ```
+/// use reformation::{Reformation, TriState};
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{name}: {enabled}")]
+/// struct Feature{
+///     name: String,
+///     enabled: TriState,
+/// }
+///
+/// fn main(){
+///     let feature: Feature = "sse4: unknown".parse().unwrap();
+///     assert_eq!(feature.enabled, TriState::Unknown);
+///     assert_eq!(feature.enabled.to_option(), None);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TriState{
+    True,
+    False,
+    Unknown,
+}
+
+impl TriState{
+    /// `Some(bool)` for `True` and `False`, `None` for `Unknown`
+    pub fn to_option(self)->Option<bool>{
+        match self{
+            TriState::True => Some(true),
+            TriState::False => Some(false),
+            TriState::Unknown => None,
+        }
+    }
+
+    /// Regular expression matching one of tokens for `True`, `False` and `Unknown`.
+    /// Longer tokens are tried first, so token may be prefix of another one.
+    #[doc(hidden)]
+    pub fn tokens_regex_str(tokens: [&str; 3])->String{
+        let mut sorted = tokens;
+        sorted.sort_by_key(|token| std::cmp::Reverse(token.len()));
+        let sorted: Vec<_> = sorted.iter().map(|token| regex::escape(token)).collect();
+        format!("({})", sorted.join("|"))
+    }
+
+    /// Value denoted by token, given tokens for `True`, `False` and `Unknown`
+    #[doc(hidden)]
+    pub fn from_token(token: &str, tokens: [&str; 3])->Result<Self, Box<dyn Error>>{
+        match tokens.iter().position(|t| *t == token){
+            Some(0) => Ok(TriState::True),
+            Some(1) => Ok(TriState::False),
+            Some(_) => Ok(TriState::Unknown),
+            None => Err(format!("Unknown tri-state value {:?}", token).into()),
+        }
+    }
+}
+
+impl From<bool> for TriState{
+    fn from(value: bool)->Self{
+        if value{ TriState::True }else{ TriState::False }
+    }
+}
+
+impl Reformation for TriState{
+    fn regex_str()->&'static str{
+        "(unknown|false|true)"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        TriState::from_token(c.get(offset).unwrap().as_str(), ["true", "false", "unknown"])
+    }
+}
+
+/// Declare newtype of `TriState`, which matches given tokens for `True`, `False`
+/// and `Unknown` respectively.
+///
+/// ```
+/// use reformation::{Reformation, TriState, tri_state};
+///
+/// tri_state!{
+///     /// Answer of survey
+///     pub Answer = "yes", "no", "n/a"
+/// }
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{question}\? {answer}")]
+/// struct Survey{
+///     question: String,
+///     answer: Answer,
+/// }
+///
+/// fn main(){
+///     let survey: Survey = "Coffee? yes".parse().unwrap();
+///     assert_eq!(survey.answer, Answer(TriState::True));
+///     let survey: Survey = "Tea? n/a".parse().unwrap();
+///     assert_eq!(survey.answer.0, TriState::Unknown);
+///     assert!("Tea? maybe".parse::<Survey>().is_err());
+/// }
+/// ```
+#[macro_export]
+macro_rules! tri_state{
+    ($(#[$meta: meta])* $vis: vis $name: ident = $true: expr, $false: expr, $unknown: expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        $vis struct $name(pub $crate::TriState);
+
+        impl $crate::Reformation for $name{
+            fn regex_str()->&'static str{
+                $crate::lazy_static!{
+                    static ref STR: String = $crate::TriState::tokens_regex_str([$true, $false, $unknown]);
+                }
+                &STR
+            }
+
+            fn captures_count()->usize{
+                1
+            }
+
+            fn from_captures(c: &$crate::Captures, offset: usize)->Result<Self, Box<dyn ::std::error::Error>>{
+                let token = c.get(offset).unwrap().as_str();
+                $crate::TriState::from_token(token, [$true, $false, $unknown]).map($name)
+            }
+        }
+    };
+}
+
+/// Declare unit struct, which matches exact literal string and captures nothing.
+/// Such struct can be used as field to require literal in generic code, or
+/// consumed by `Tokenizer`.
+///
+/// ```
+/// use reformation::{Reformation, Tokenizer, literal};
+///
+/// literal!{
+///     /// Keyword `PING`
+///     pub Ping = "PING"
+/// }
+///
+/// #[derive(Reformation)]
+/// #[reformation(r"{ping} {id}")]
+/// struct Request{
+///     ping: Ping,
+///     id: u32,
+/// }
+///
+/// fn main(){
+///     let request: Request = "PING 42".parse().unwrap();
+///     assert_eq!((request.ping, request.id), (Ping, 42));
+///     assert!("PONG 42".parse::<Request>().is_err());
+///
+///     let mut tokens = Tokenizer::new("PING PING?");
+///     assert_eq!(tokens.next::<Ping>().unwrap(), Ping);
+///     assert_eq!(tokens.next::<Ping>().unwrap(), Ping);
+///     assert!(tokens.next::<Ping>().is_err());
+/// }
+/// ```
+#[macro_export]
+macro_rules! literal{
+    ($(#[$meta: meta])* $vis: vis $name: ident = $literal: expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+        $vis struct $name;
+
+        impl $crate::Reformation for $name{
+            fn regex_str()->&'static str{
+                $crate::lazy_static!{
+                    static ref STR: String = format!("(?:{})", $crate::escape($literal));
+                }
+                &STR
+            }
+
+            fn captures_count()->usize{
+                0
+            }
+
+            fn from_captures(_c: &$crate::Captures, _offset: usize)->Result<Self, Box<dyn ::std::error::Error>>{
+                Ok($name)
+            }
+        }
+    };
+}
@@ -0,0 +1,104 @@
+use reformation::Reformation;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Allocator counting allocations made by current thread
+struct Counting;
+
+thread_local!{
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting{
+    unsafe fn alloc(&self, layout: Layout)->*mut u8{
+        ALLOCATIONS.with(|x| x.set(x.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout){
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations()->usize{
+    ALLOCATIONS.with(|x| x.get())
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{x}:{y}$", try_parse_ref)]
+struct Point{
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_try_parse_ref_failure_does_not_allocate(){
+    // compile regular expression
+    assert_eq!(Point::try_parse_ref("1:2").unwrap(), Point{x: 1, y: 2});
+    assert!(Point::try_parse_ref("1-2").is_err());
+
+    let input = "1-2";
+    let before = allocations();
+    let err = Point::try_parse_ref(input);
+    assert_eq!(allocations(), before);
+
+    let err = err.unwrap_err();
+    assert_eq!(err.request, input);
+    assert_eq!(err.format, Point::regex_str());
+}
+
+#[test]
+fn test_try_parse_ref_invalid_value(){
+    // input matches, but `y` overflows: reported same way as unmatched input
+    let input = "1:99999999999";
+    let err = Point::try_parse_ref(input).unwrap_err();
+    assert_eq!(err.request, input);
+    assert_eq!(err.format, Point::regex_str());
+    assert!(input.parse::<Point>().unwrap_err().downcast_ref::<std::num::ParseIntError>().is_some());
+}
+
+#[derive(Reformation, Debug, PartialEq, Default)]
+#[reformation(r"^{level} \[{module}\] {message}$", reparse)]
+struct Record{
+    level: u8,
+    module: String,
+    #[reformation(max_len = 64)]
+    message: String,
+}
+
+#[test]
+fn test_reparse_reuses_buffers(){
+    let lines = ["1 [net] connection opened", "2 [db] query took 3ms", "1 [net] closed"];
+    let mut record = Record::default();
+    record.reparse("0 [warm up the buffers and regex] with a reasonably long message").unwrap();
+
+    let before = allocations();
+    for line in &lines{
+        let parsed: Record = line.parse().unwrap();
+        assert_eq!(parsed.level, 1 + (parsed.module == "db") as u8);
+    }
+    let parse_allocations = allocations() - before;
+
+    for line in &lines{
+        record.reparse(line).unwrap();
+        assert_eq!(record, line.parse().unwrap());
+    }
+
+    let before = allocations();
+    for line in &lines{
+        record.reparse(line).unwrap();
+    }
+    let reparse_allocations = allocations() - before;
+    // only capture slots are allocated
+    assert!(reparse_allocations <= lines.len(), "{}", reparse_allocations);
+    assert!(reparse_allocations < parse_allocations);
+    assert_eq!(record, Record{level: 1, module: "net".to_string(), message: "closed".to_string()});
+
+    // failed parse keeps value unchanged
+    assert!(record.reparse("300 [x] y").is_err());
+    assert!(record.reparse("nonsense").is_err());
+    assert_eq!(record, Record{level: 1, module: "net".to_string(), message: "closed".to_string()});
+}
@@ -0,0 +1,40 @@
+use reformation::{Reformation, Captures, ClockTime};
+use std::error::Error;
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{time} {value}")]
+struct Record{
+    time: ClockTime,
+    value: Option<(i32, i32)>,
+}
+
+/// `captures_count` does not account for group around sign
+struct Broken(i32);
+
+impl Reformation for Broken{
+    fn regex_str()->&'static str{
+        r"([\+-])?(\d+)"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(Broken(c.get(offset).map(|x| x.as_str()).unwrap_or("").parse()?))
+    }
+}
+
+#[test]
+fn test_audit(){
+    assert_eq!(reformation::audit::<Record>(), Ok(()));
+    let r: Record = "12:00:00 (1, 2)".parse().unwrap();
+    assert_eq!((r.time.0.as_secs(), r.value), (12 * 3600, Some((1, 2))));
+
+    let err = reformation::audit::<Broken>().unwrap_err();
+    assert!(err.contains("has 2 capture groups, but `captures_count` is 1"), "{}", err);
+    // the bug audit detects: value is read from wrong group
+    let re = reformation::Regex::new(Broken::regex_str()).unwrap();
+    let captures = re.captures("7").unwrap();
+    assert!(Broken::from_captures(&captures, 1).map(|b| b.0).is_err());
+}
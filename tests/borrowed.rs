@@ -0,0 +1,68 @@
+use reformation::ReformationRef;
+
+#[derive(ReformationRef, Debug, PartialEq)]
+#[reformation(r"^{name}: {n}$")]
+struct Ref<'a>{
+    name: &'a str,
+    n: u32,
+}
+
+#[derive(ReformationRef, Debug, PartialEq)]
+#[reformation(r"{key}(?:={value})?$", prefix = "--", greedy = false)]
+struct Arg<'a>{
+    key: &'a str,
+    value: Option<&'a str>,
+}
+
+#[derive(ReformationRef, Debug)]
+#[reformation(r"^{id} {user} {code}$")]
+struct Line<'input>{
+    #[reformation(radix = 16)]
+    id: u32,
+    user: &'input str,
+    #[reformation(checked)]
+    code: u8,
+}
+
+#[test]
+fn test_borrowed_fields(){
+    let input = String::from("first name: 42");
+    let r = Ref::parse(&input).unwrap();
+    assert_eq!(r, Ref{name: "first name", n: 42});
+    // slice points into input, rather than into its copy
+    assert_eq!(r.name.as_ptr(), input.as_ptr());
+
+    assert!(Ref::parse("name: x").is_err());
+    let err = Ref::parse("name").unwrap_err();
+    assert!(err.downcast_ref::<reformation::NoRegexMatch>().is_some());
+}
+
+#[test]
+fn test_borrowed_optional(){
+    assert_eq!(Arg::parse("--verbose").unwrap(), Arg{key: "verbose", value: None});
+    assert_eq!(Arg::parse("--level=3").unwrap(), Arg{key: "level", value: Some("3")});
+    assert_eq!(Arg::parse("--level=").unwrap(), Arg{key: "level", value: Some("")});
+    assert_eq!(Arg::regex_str(), r"\-\-(.*?)(?:=(.*?))?$");
+}
+
+#[test]
+fn test_borrowed_with_attributes(){
+    let line = Line::parse("0x1f root 200").unwrap();
+    assert_eq!((line.id, line.user, line.code), (31, "root", 200));
+    let err = Line::parse("1f root 300").unwrap_err().to_string();
+    assert_eq!(err, "Field `code`: value 300 overflows type `u8`");
+}
+
+#[derive(ReformationRef, Debug, PartialEq)]
+#[reformation(r"^{cmd}(?: {arg})?", string_default = "non_ws")]
+struct Command<'a>{
+    cmd: &'a str,
+    arg: Option<&'a str>,
+}
+
+#[test]
+fn test_borrowed_string_default(){
+    assert_eq!(Command::regex_str(), r"^(\S+)(?: (\S+))?");
+    assert_eq!(Command::parse("ls -la /tmp").unwrap(), Command{cmd: "ls", arg: Some("-la")});
+    assert_eq!(Command::parse("ls").unwrap(), Command{cmd: "ls", arg: None});
+}
@@ -0,0 +1,135 @@
+use reformation::Reformation;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^\[{items}\]$")]
+struct Forbid{
+    #[reformation(separator = ",")]
+    items: Vec<u32>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^\[{items}\]$")]
+struct Allow{
+    #[reformation(separator = ",", trailing_separator = "allow")]
+    items: Vec<u32>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^\[{items}\]$")]
+struct Require{
+    #[reformation(separator = ",", trailing_separator = "require")]
+    items: Vec<u32>,
+}
+
+#[test]
+fn test_trailing_separator_forbid(){
+    assert_eq!("[1,2,3]".parse::<Forbid>().unwrap().items, vec![1, 2, 3]);
+    assert!("[1,2,3,]".parse::<Forbid>().is_err());
+    assert_eq!("[]".parse::<Forbid>().unwrap().items, vec![]);
+}
+
+#[test]
+fn test_trailing_separator_allow(){
+    assert_eq!("[1,2,3]".parse::<Allow>().unwrap().items, vec![1, 2, 3]);
+    assert_eq!("[1,2,3,]".parse::<Allow>().unwrap().items, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_trailing_separator_require(){
+    assert!("[1,2,3]".parse::<Require>().is_err());
+    assert_eq!("[1,2,3,]".parse::<Require>().unwrap().items, vec![1, 2, 3]);
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^tags: {tags}$")]
+struct Tags{
+    #[reformation(separator = ",")]
+    tags: HashSet<String>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^tags: {tags}$")]
+struct UniqueTags{
+    #[reformation(separator = ",", duplicates = "error")]
+    tags: HashSet<String>,
+}
+
+fn set(items: &[&str])->HashSet<String>{
+    items.iter().map(|x| x.to_string()).collect()
+}
+
+#[test]
+fn test_hash_set_dedupe(){
+    assert_eq!("tags: a,b,c".parse::<Tags>().unwrap().tags, set(&["a", "b", "c"]));
+    assert_eq!("tags: a,b,a,c".parse::<Tags>().unwrap().tags, set(&["a", "b", "c"]));
+}
+
+#[test]
+fn test_hash_set_duplicates_error(){
+    assert_eq!("tags: a,b,c".parse::<UniqueTags>().unwrap().tags, set(&["a", "b", "c"]));
+    assert!("tags: a,b,a,c".parse::<UniqueTags>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^ports: {ports}$")]
+struct Ports{
+    #[reformation(separator = ", ")]
+    ports: BTreeSet<u16>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^ports: {ports}$")]
+struct UniquePorts{
+    #[reformation(separator = ", ", duplicates = "error")]
+    ports: BTreeSet<u16>,
+}
+
+#[test]
+fn test_btree_set(){
+    let ports = "ports: 443, 80, 8080, 80, 22".parse::<Ports>().unwrap().ports;
+    assert_eq!(ports.into_iter().collect::<Vec<_>>(), vec![22, 80, 443, 8080]);
+    assert!("ports: ".parse::<Ports>().unwrap().ports.is_empty());
+    assert!("ports: 443, 80, 80".parse::<UniquePorts>().is_err());
+    let ports = "ports: 443, 80".parse::<UniquePorts>().unwrap().ports;
+    assert_eq!(ports.into_iter().collect::<Vec<_>>(), vec![80, 443]);
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^queue: {jobs}$")]
+struct Queue{
+    #[reformation(separator = " ")]
+    jobs: VecDeque<u32>,
+}
+
+#[test]
+fn test_vec_deque(){
+    let mut q: Queue = "queue: 3 1 2".parse().unwrap();
+    assert_eq!(q.jobs.pop_front(), Some(3));
+    assert_eq!(q.jobs, VecDeque::from(vec![1, 2]));
+    assert!("queue: ".parse::<Queue>().unwrap().jobs.is_empty());
+    assert!("queue: 1  2".parse::<Queue>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"\({x}, {y}\)")]
+struct Point{
+    x: i32,
+    y: i32,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^path: {points}$")]
+struct Path{
+    #[reformation(separator = ", ", trailing_separator = "allow")]
+    points: Vec<Point>,
+}
+
+#[test]
+fn test_items_containing_separator(){
+    let path: Path = "path: (1, 2), (-3, 4), (5, 60)".parse().unwrap();
+    assert_eq!(path.points, vec![Point{x: 1, y: 2}, Point{x: -3, y: 4}, Point{x: 5, y: 60}]);
+    let path: Path = "path: (1, 2), ".parse().unwrap();
+    assert_eq!(path.points, vec![Point{x: 1, y: 2}]);
+    assert!("path: (1, 2), (3)".parse::<Path>().is_err());
+}
@@ -0,0 +1,769 @@
+use reformation::Reformation;
+use std::convert::TryFrom;
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{id}(?: x{count})?$")]
+struct Item{
+    id: u32,
+    #[reformation(default_if_absent)]
+    count: u32,
+}
+
+#[test]
+fn test_default_if_absent(){
+    let item: Item = "12 x3".parse().unwrap();
+    assert_eq!(item, Item{id: 12, count: 3});
+    let item: Item = "12".parse().unwrap();
+    assert_eq!(item, Item{id: 12, count: 0});
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{id}(?: x{count})?(?: by {owner})?$")]
+struct Order{
+    id: u32,
+    #[reformation(default = "1")]
+    count: u32,
+    #[reformation(default = "\"nobody\"")]
+    owner: String,
+    #[reformation(default = "42")]
+    priority: u8,
+    #[reformation(default = "\"new\"")]
+    status: String,
+}
+
+#[test]
+fn test_default_value(){
+    let order: Order = "12 x3 by alice".parse().unwrap();
+    assert_eq!(order, Order{id: 12, count: 3, owner: "alice".to_string(), priority: 42, status: "new".to_string()});
+    let order: Order = "12".parse().unwrap();
+    assert_eq!(order, Order{id: 12, count: 1, owner: "nobody".to_string(), priority: 42, status: "new".to_string()});
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{c}/{b}/{a}")]
+struct Reversed{
+    a: u32,
+    b: String,
+    c: f32,
+}
+
+#[test]
+fn test_fields_in_reverse_order(){
+    let r: Reversed = "1.5/xyz/3".parse().unwrap();
+    assert_eq!(r, Reversed{a: 3, b: "xyz".to_string(), c: 1.5});
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{year}-{month}-{day} {hour}:{minute}", try_from)]
+struct Date{
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+}
+
+#[test]
+fn test_date(){
+    let date: Date = "2018-12-22 20:23".parse().unwrap();
+    assert_eq!((date.year, date.month, date.day), (2018, 12, 22));
+    assert_eq!((date.hour, date.minute), (20, 23));
+}
+
+#[test]
+fn test_try_from(){
+    let date = Date::try_from(String::from("2018-12-22 20:23")).unwrap();
+    assert_eq!((date.year, date.month, date.day), (2018, 12, 22));
+    let s = String::from("1999-01-02 03:04");
+    let date = Date::try_from(&s).unwrap();
+    assert_eq!((date.hour, date.minute), (3, 4));
+    let date = Date::try_from("2000-02-29 00:00").unwrap();
+    assert_eq!(date.day, 29);
+    assert!(Date::try_from(String::from("2018-12-22")).is_err());
+}
+
+#[test]
+fn test_explain(){
+    let explanation = Date::explain();
+    for field in &["{year}: u16", "{month}: u8", "{day}: u8", "{hour}: u8", "{minute}: u8"]{
+        assert!(explanation.contains(field), "{} not found in {}", field, explanation);
+    }
+    for literal in &[r#"literal r"-""#, r#"literal r" ""#, r#"literal r":""#]{
+        assert!(explanation.contains(literal), "{} not found in {}", literal, explanation);
+    }
+    assert!(explanation.contains("groups 5..6"));
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{x}, {y}", prefix = "[", suffix = "]")]
+struct Bracketed{
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_prefix_suffix(){
+    let b: Bracketed = "[1, 2]".parse().unwrap();
+    assert_eq!(b, Bracketed{x: 1, y: 2});
+    assert!("1, 2".parse::<Bracketed>().is_err());
+    assert!("[1, 2".parse::<Bracketed>().is_err());
+    assert!("1, 2]".parse::<Bracketed>().is_err());
+}
+
+fn is_month(month: &u8)->bool{
+    (1..=12).contains(month)
+}
+
+fn is_day(day: &u8)->Result<(), String>{
+    if (1..=31).contains(day){
+        Ok(())
+    }else{
+        Err(format!("day {} is out of range 1..=31", day))
+    }
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{month}/{day}")]
+struct MonthDay{
+    #[reformation(validate = is_month)]
+    month: u8,
+    #[reformation(validate = is_day)]
+    day: u8,
+}
+
+#[test]
+fn test_validate(){
+    assert_eq!("12/31".parse::<MonthDay>().unwrap(), MonthDay{month: 12, day: 31});
+
+    let err = "13/1".parse::<MonthDay>().unwrap_err().to_string();
+    assert!(err.contains("Field `month` failed validation `is_month`"), "{}", err);
+
+    let err = "1/32".parse::<MonthDay>().unwrap_err().to_string();
+    assert!(err.contains("Field `day` failed validation `is_day`: day 32 is out of range 1..=31"), "{}", err);
+}
+
+#[test]
+fn test_derive_inside_function(){
+    // local module shadows crate name, generated code must not depend on it
+    #[allow(dead_code)]
+    mod reformation{}
+
+    #[derive(::reformation::Reformation, Debug, PartialEq)]
+    #[reformation(r"{a}\+{b}")]
+    struct Sum{
+        a: i32,
+        b: i32,
+    }
+
+    assert_eq!("1+2".parse::<Sum>().unwrap(), Sum{a: 1, b: 2});
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^run {verbose}\s*{file}$")]
+struct Run{
+    #[reformation(flag = "--verbose")]
+    verbose: bool,
+    file: String,
+}
+
+#[test]
+fn test_flag(){
+    let r: Run = "run --verbose main.rs".parse().unwrap();
+    assert_eq!(r, Run{verbose: true, file: "main.rs".to_string()});
+    let r: Run = "run main.rs".parse().unwrap();
+    assert_eq!(r, Run{verbose: false, file: "main.rs".to_string()});
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{year}-{month}-{day}", r"{day}\.{month}\.{year}", prefix = "date: ", is)]
+struct AnyDate{
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+#[test]
+fn test_alternative_formats(){
+    let expected = AnyDate{year: 2018, month: 12, day: 22};
+    assert_eq!("date: 2018-12-22".parse::<AnyDate>().unwrap(), expected);
+    assert_eq!("date: 22.12.2018".parse::<AnyDate>().unwrap(), expected);
+    assert!("date: 22/12/2018".parse::<AnyDate>().is_err());
+    assert_eq!(AnyDate::captures_count(), 8);
+
+    let explanation = AnyDate::explain();
+    assert!(explanation.contains("alternative 1: group 5"), "{}", explanation);
+    assert!(explanation.contains("{year}: u16 = r\"(\\\\d+)\", groups 8..9"), "{}", explanation);
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"Vec\{{\s*{x}\s*,\s+{y}\s*\}}", canonical)]
+struct Vector{
+    x: i32,
+    y: f64,
+}
+
+#[test]
+fn test_canonical(){
+    let v: Vector = "Vec{  1 ,   -2.5 }".parse().unwrap();
+    assert_eq!(v.canonical(), "Vec{1, -2.5}");
+    let v: Vector = v.canonical().parse().unwrap();
+    assert_eq!((v.x, v.y), (1, -2.5));
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{key}={value}")]
+struct Assignment{
+    key: reformation::AsciiChar,
+    value: i32,
+    #[reformation(capture = "whole")]
+    raw: String,
+}
+
+#[test]
+fn test_capture_whole(){
+    let a: Assignment = "let x=42;".parse().unwrap();
+    assert_eq!(a, Assignment{key: reformation::AsciiChar(b'x'), value: 42, raw: "x=42".to_string()});
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^(?P<key>[a-z_]+)\s*(=|:)\s*(?P<value>-?\d+)(?:\s+#(?P<comment>.*))?$", raw)]
+struct Setting{
+    key: String,
+    #[reformation(checked)]
+    value: i16,
+    comment: Option<String>,
+}
+
+#[test]
+fn test_raw_regex(){
+    let s: Setting = "max_width = 80".parse().unwrap();
+    assert_eq!(s, Setting{key: "max_width".to_string(), value: 80, comment: None});
+    let s: Setting = "depth: -3 #negative".parse().unwrap();
+    assert_eq!(s, Setting{key: "depth".to_string(), value: -3, comment: Some("negative".to_string())});
+    assert!("depth = x".parse::<Setting>().is_err());
+    assert!("depth = 70000".parse::<Setting>().is_err());
+    assert_eq!(Setting::captures_count(), 4);
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{key}:{value}$")]
+struct GreedyPair{
+    key: String,
+    value: String,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{key}:{value}$", greedy = false)]
+struct LazyPair{
+    key: String,
+    value: String,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{a}{b}{c}!$", greedy = false)]
+struct Repeated{
+    a: String,
+    b: String,
+    c: String,
+}
+
+#[test]
+fn test_greedy_false(){
+    let p: GreedyPair = "a:b:c".parse().unwrap();
+    assert_eq!((p.key.as_str(), p.value.as_str()), ("a:b", "c"));
+    let p: LazyPair = "a:b:c".parse().unwrap();
+    assert_eq!((p.key.as_str(), p.value.as_str()), ("a", "b:c"));
+    assert_eq!(LazyPair::regex_str(), "^(.*?):(.*?)$");
+
+    // every lazy field takes as little as possible, the last one takes the rest
+    assert_eq!(Repeated::regex_str(), "^(.*?)(.*?)(.*?)!$");
+    let r: Repeated = "xyz!".parse().unwrap();
+    assert_eq!((r.a.as_str(), r.b.as_str(), r.c.as_str()), ("", "", "xyz"));
+    let r: Repeated = "x!y!".parse().unwrap();
+    assert_eq!((r.a.as_str(), r.b.as_str(), r.c.as_str()), ("", "", "x!y"));
+    assert!("x".repeat(1_000).parse::<Repeated>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{cmd} {arg}", string_default = "greedy")]
+struct GreedyCommand{
+    cmd: String,
+    arg: String,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{cmd} {arg}", string_default = "lazy")]
+struct LazyCommand{
+    cmd: String,
+    arg: String,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{cmd} {arg}", string_default = "non_ws")]
+struct WordCommand{
+    cmd: String,
+    arg: String,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{cmd} {arg}$", greedy = false, string_default = "non_ws")]
+struct LazyWordCommand{
+    cmd: String,
+    arg: u32,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{cmd}(?: {arg})?", string_default = "non_ws")]
+struct OptionalArgCommand{
+    cmd: String,
+    arg: Option<String>,
+}
+
+#[test]
+fn test_string_default(){
+    let c: GreedyCommand = "ls -la /tmp".parse().unwrap();
+    assert_eq!((c.cmd.as_str(), c.arg.as_str()), ("ls -la", "/tmp"));
+    let c: LazyCommand = "ls -la /tmp".parse().unwrap();
+    assert_eq!((c.cmd.as_str(), c.arg.as_str()), ("ls", ""));
+    let c: WordCommand = "ls -la /tmp".parse().unwrap();
+    assert_eq!((c.cmd.as_str(), c.arg.as_str()), ("ls", "-la"));
+    assert!(" -la".parse::<WordCommand>().is_err());
+    assert_eq!(LazyWordCommand::regex_str(), r"^(\S+) (\d+?)$");
+    let c: LazyWordCommand = "kill 9".parse().unwrap();
+    assert_eq!((c.cmd.as_str(), c.arg), ("kill", 9));
+    assert_eq!(OptionalArgCommand::regex_str(), r"^(\S+)(?: ((\S+))?)?");
+    let c: OptionalArgCommand = "ls -la /tmp".parse().unwrap();
+    assert_eq!((c.cmd.as_str(), c.arg.as_deref()), ("ls", Some("-la")));
+    let c: OptionalArgCommand = "ls".parse().unwrap();
+    assert_eq!((c.cmd.as_str(), c.arg), ("ls", None));
+}
+
+#[test]
+fn test_parse_opt(){
+    let p = GreedyPair::parse_opt("a:b").unwrap();
+    assert_eq!((p.key.as_str(), p.value.as_str()), ("a", "b"));
+    assert!(GreedyPair::parse_opt("ab").is_none());
+    // value of field fails validation
+    assert!(MonthDay::parse_opt("13/01").is_none());
+    assert!(MonthDay::parse_opt("12/01").is_some());
+    assert_eq!(Setting::parse_opt("depth: -3").map(|s| s.value), Some(-3));
+    assert!(Setting::parse_opt("depth = 70000").is_none());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^\w+ {value}: {comment}$", unicode = false)]
+struct AsciiWord{
+    value: u32,
+    comment: String,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^\w+ {value}: {comment}$")]
+struct UnicodeWord{
+    value: u32,
+    comment: String,
+}
+
+#[test]
+fn test_unicode_false(){
+    assert!(AsciiWord::regex_str().starts_with("(?-u:"));
+    let w: AsciiWord = "hello 5: ünïcode".parse().unwrap();
+    assert_eq!((w.value, w.comment.as_str()), (5, "ünïcode"));
+    assert!("héllo 5: x".parse::<AsciiWord>().is_err());
+    let w: UnicodeWord = "héllo 5: x".parse().unwrap();
+    assert_eq!((w.value, w.comment.as_str()), (5, "x"));
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{sign}{magnitude}$", unicode = false)]
+struct AsciiSigned{
+    sign: reformation::Sign,
+    magnitude: u32,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^UTC{offset_value}$", unicode = false)]
+struct AsciiZone{
+    offset_value: reformation::SignedDuration,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{lower}, {upper}$", unicode = false)]
+struct AsciiInterval{
+    lower: std::ops::Bound<i32>,
+    upper: u32,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{value}$", unicode = false)]
+struct AsciiMinus{
+    #[reformation(unicode_minus)]
+    value: i32,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{cmd} {arg}", unicode = false, string_default = "non_ws")]
+struct AsciiCommand{
+    cmd: String,
+    arg: String,
+}
+
+#[test]
+fn test_unicode_false_non_ascii_types(){
+    let s: AsciiSigned = "±5".parse().unwrap();
+    assert_eq!((s.sign, s.magnitude), (reformation::Sign::PlusMinus, 5));
+    let z: AsciiZone = "UTC\u{2212}05:30".parse().unwrap();
+    assert_eq!(z.offset_value, reformation::SignedDuration(-19800));
+    let i: AsciiInterval = "(∞, 5".parse().unwrap();
+    assert_eq!((i.lower, i.upper), (std::ops::Bound::Unbounded, 5));
+    let m: AsciiMinus = "\u{2212}7".parse().unwrap();
+    assert_eq!(m.value, -7);
+    let c: AsciiCommand = "ls ünï x".parse().unwrap();
+    assert_eq!((c.cmd.as_str(), c.arg.as_str()), ("ls", "ünï"));
+}
+
+#[test]
+fn test_is(){
+    let expected = AnyDate{year: 2018, month: 12, day: 22};
+    assert!(AnyDate::is("date: 2018-12-22", &expected));
+    assert!(AnyDate::is("date: 22.12.2018", &expected));
+    assert!(!AnyDate::is("date: 2018-12-23", &expected));
+    assert!(!AnyDate::is("2018-12-22", &expected));
+}
+
+#[test]
+fn test_explain_failure(){
+    assert_eq!(Date::explain_failure("2018-12-22 2x:23"), "2018-12-22 2x:23\n            ^");
+    assert_eq!(AnyDate::explain_failure("date: 22.12/2018"), "date: 22.12/2018\n           ^");
+    assert_eq!(AnyDate::explain_failure("time: 12"), "time: 12\n^");
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{id}: (?P<hex>[0-9a-f]{{6}}) {comment}$")]
+struct ColorEntry{
+    id: u32,
+    hex: String,
+    comment: String,
+}
+
+#[test]
+fn test_named_group_binding(){
+    let c: ColorEntry = "7: ff00aa pink".parse().unwrap();
+    assert_eq!(c, ColorEntry{id: 7, hex: "ff00aa".to_string(), comment: "pink".to_string()});
+    assert!("7: ff00a pink".parse::<ColorEntry>().is_err());
+    assert_eq!(ColorEntry::captures_count(), 3);
+    assert_eq!(reformation::audit::<ColorEntry>(), Ok(()));
+    assert!(ColorEntry::explain().contains("(?P<hex>): String, group 2"), "{}", ColorEntry::explain());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{id}: (?P<pair>.*)$")]
+struct PairEntry{
+    #[allow(dead_code)]
+    id: u32,
+    #[allow(dead_code)]
+    pair: (u8, u8),
+}
+
+#[test]
+fn test_named_group_single_capture(){
+    let e = "7: (1, 2)".parse::<PairEntry>().unwrap_err();
+    assert!(e.to_string().contains("Field `pair` bound to named group must have type matching single capture group"), "{}", e);
+}
+
+#[test]
+fn test_min_len(){
+    // "-", "-", " " and ":"
+    assert_eq!(Date::min_len(), 4);
+    assert_eq!(Item::min_len(), 0);
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{a}b{dots}$")]
+struct Repetitions{
+    #[reformation(count = 'a')]
+    a: usize,
+    #[reformation(count = '.')]
+    dots: usize,
+}
+
+#[test]
+fn test_count(){
+    let r: Repetitions = "aaab".parse().unwrap();
+    assert_eq!((r.a, r.dots), (3, 0));
+    let r: Repetitions = "b..".parse().unwrap();
+    assert_eq!((r.a, r.dots), (0, 2));
+    assert!("aab.x".parse::<Repetitions>().is_err());
+    assert!("".parse::<Repetitions>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^\[{errors}\] {retries}$")]
+struct Summary{
+    #[reformation(count_of = "E")]
+    errors: usize,
+    #[reformation(count_of = r"retry #\d+")]
+    retries: usize,
+}
+
+#[test]
+fn test_count_of(){
+    let s: Summary = "[EWEEW] retry #1, timeout, retry #12".parse().unwrap();
+    assert_eq!((s.errors, s.retries), (3, 2));
+    let s: Summary = "[] ok".parse().unwrap();
+    assert_eq!((s.errors, s.retries), (0, 0));
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{unclosed}$")]
+struct InvalidCount{
+    #[reformation(count_of = "(")]
+    unclosed: usize,
+}
+
+#[test]
+#[should_panic(expected = "Cannot compile regex of `count_of` of field `unclosed`")]
+fn test_count_of_invalid(){
+    let _ = "((".parse::<InvalidCount>().map(|c| c.unclosed);
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{name} {_:u32} {score}(?: \({_}\))?$")]
+struct Player{
+    name: String,
+    score: f32,
+}
+
+#[test]
+fn test_discarded_placeholder(){
+    assert_eq!(Player::captures_count(), 4);
+    let p: Player = "alice 17 2.5".parse().unwrap();
+    assert_eq!(p, Player{name: "alice".to_string(), score: 2.5});
+    let p: Player = "bob 3 -1 (retired)".parse().unwrap();
+    assert_eq!(p, Player{name: "bob".to_string(), score: -1.0});
+    assert!("alice x 2.5".parse::<Player>().is_err());
+    assert!("alice 99999999999 2.5".parse::<Player>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{name}: {kind}(?: = {value})?$", greedy = false)]
+struct Variable{
+    name: String,
+    kind: String,
+    #[reformation(when = "kind == \"int\"")]
+    value: Option<i64>,
+}
+
+#[test]
+fn test_when(){
+    let v: Variable = "x: int = -5".parse().unwrap();
+    assert_eq!(v.value, Some(-5));
+    let v: Variable = "s: str".parse().unwrap();
+    assert_eq!((v.kind.as_str(), v.value), ("str", None));
+
+    let err = "x: int".parse::<Variable>().unwrap_err().to_string();
+    assert!(err.contains("Field `value` is required when `kind == \"int\"`"), "{}", err);
+    let err = "s: str = 1".parse::<Variable>().unwrap_err().to_string();
+    assert!(err.contains("Field `value` is not allowed unless `kind == \"int\"`"), "{}", err);
+}
+
+#[derive(Reformation, Debug, PartialEq, Clone, Copy)]
+#[reformation(r"\({x}, {y}\)")]
+struct Point{
+    x: i32,
+    y: i32,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{a} {b} {c}$")]
+struct Triangle{
+    a: Point,
+    b: Point,
+    c: Point,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Shape{
+    #[reformation(r"segment {} {}")]
+    Segment(Point, Point),
+    #[reformation(r"triangle {} {} {}")]
+    Triangle(Point, Point, Point),
+}
+
+#[test]
+fn test_same_type_fields(){
+    let (a, b, c) = (Point{x: 1, y: -2}, Point{x: 30, y: 40}, Point{x: -500, y: 600});
+    assert_eq!(Triangle::captures_count(), 6);
+    let t: Triangle = "(1, -2) (30, 40) (-500, 600)".parse().unwrap();
+    assert_eq!(t, Triangle{a, b, c});
+
+    assert_eq!("triangle (1, -2) (30, 40) (-500, 600)".parse::<Shape>().unwrap(), Shape::Triangle(a, b, c));
+    assert_eq!("segment (30, 40) (1, -2)".parse::<Shape>().unwrap(), Shape::Segment(b, a));
+}
+
+/// Attributes of struct and fields are interleaved with other attributes
+#[derive(Reformation)]
+#[reformation(error = reformation::ReformationError)]
+#[derive(Debug)]
+#[allow(dead_code)]
+/// format goes after other arguments
+#[reformation(r"^{id}: {balance}(?: x{count})?$")]
+struct Interleaved{
+    /// identifier
+    #[reformation(radix = 16)]
+    #[allow(unused)]
+    id: u32,
+    #[doc = "balance"]
+    #[reformation(accounting)]
+    #[reformation(checked)]
+    balance: i8,
+    #[reformation(default_if_absent)]
+    /// count
+    count: u32,
+}
+
+#[test]
+fn test_attribute_order(){
+    let v: Interleaved = "ff: (12) x3".parse().unwrap();
+    assert_eq!((v.id, v.balance, v.count), (255, -12, 3));
+    let v: Interleaved = "0x10: 7".parse().unwrap();
+    assert_eq!((v.id, v.balance, v.count), (16, 7, 0));
+    match "1: (300)".parse::<Interleaved>(){
+        Err(reformation::ReformationError::InvalidValue{error, ..}) => {
+            assert_eq!(error.to_string(), "Field `balance`: value (300) overflows type `i8`");
+        },
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{name} #{id}(?: \[{tags}\])?: {_:u8} [^ ]+ {value}$")]
+struct Compact{
+    name: String,
+    #[reformation(radix = 16)]
+    id: u32,
+    #[reformation(separator = ", ")]
+    tags: Vec<String>,
+    value: f32,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{name} #{id}(?: \[{tags}\])?: {_:u8} [^ ]+ {value}$", verbose_pattern)]
+struct Verbose{
+    name: String,
+    #[reformation(radix = 16)]
+    id: u32,
+    #[reformation(separator = ", ")]
+    tags: Vec<String>,
+    value: f32,
+}
+
+#[test]
+fn test_verbose_pattern(){
+    let pattern = Verbose::regex_str();
+    assert!(pattern.starts_with("(?x:\n"), "{}", pattern);
+    assert!(pattern.contains("  # id: u32\n"), "{}", pattern);
+    assert!(pattern.contains("  # _: u8\n"), "{}", pattern);
+    assert!(Verbose::explain().contains("# tags: Vec<String>"));
+    assert_eq!(Verbose::captures_count(), Compact::captures_count());
+
+    for input in &[
+        "first item #ff [a b, c]: 7 x 1.5",
+        "first #1: 0 y -2",
+        " #1: 0 y -2",
+        "a #1 [x]: 0 y 3",
+    ]{
+        let compact = input.parse::<Compact>().unwrap();
+        let verbose = input.parse::<Verbose>().unwrap();
+        assert_eq!((compact.name, compact.id, compact.tags, compact.value), (verbose.name, verbose.id, verbose.tags, verbose.value));
+    }
+    for input in &["first #1: 0 y y -2", "first#1: 0 y -2", "a #1 [x]: 0 y 3 ", "a #1: 300 y 3"]{
+        assert!(input.parse::<Compact>().is_err());
+        assert!(input.parse::<Verbose>().is_err());
+    }
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{major}\.{minor}(?:\.{patch})?$")]
+#[non_exhaustive]
+struct Version{
+    major: u32,
+    minor: u32,
+    patch: Option<u32>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[non_exhaustive]
+enum Channel{
+    #[reformation("stable")]
+    Stable,
+    #[reformation("nightly-{}")]
+    Nightly(u32),
+}
+
+#[test]
+fn test_non_exhaustive(){
+    let v: Version = "1.2".parse().unwrap();
+    assert_eq!((v.major, v.minor, v.patch), (1, 2, None));
+    let v: Version = "1.2.3".parse().unwrap();
+    assert_eq!(v.patch, Some(3));
+    assert!("1".parse::<Version>().is_err());
+
+    assert_eq!("stable".parse::<Channel>().unwrap(), Channel::Stable);
+    assert_eq!("nightly-20".parse::<Channel>().unwrap(), Channel::Nightly(20));
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{type}: {name} as {as}", canonical)]
+struct Declaration{
+    r#type: String,
+    name: String,
+    r#as: String,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^(?P<type>\w+)=(?P<value>\d+)$", raw)]
+struct RawKeyword{
+    r#type: String,
+    value: u32,
+}
+
+#[test]
+fn test_raw_identifiers(){
+    let d: Declaration = "u32: count as n".parse().unwrap();
+    assert_eq!(d, Declaration{r#type: "u32".to_string(), name: "count".to_string(), r#as: "n".to_string()});
+    assert_eq!(d.canonical(), "u32: count as n");
+
+    let k: RawKeyword = "width=3".parse().unwrap();
+    assert_eq!(k, RawKeyword{r#type: "width".to_string(), value: 3});
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"id{id}", word_boundaries)]
+struct Token{
+    id: u32,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"id{id}", word_start)]
+struct TokenStart{
+    id: u32,
+}
+
+#[test]
+fn test_word_boundaries(){
+    assert_eq!(Token::regex_str(), r"\bid(\d+)\b");
+    assert_eq!(Token::min_len(), 2);
+    assert_eq!("see id42 here".parse::<Token>().unwrap(), Token{id: 42});
+    assert_eq!("id7".parse::<Token>().unwrap(), Token{id: 7});
+    assert!("valid42".parse::<Token>().is_err());
+    assert!("id42x".parse::<Token>().is_err());
+    assert_eq!("id42x".parse::<TokenStart>().unwrap(), TokenStart{id: 42});
+    assert!("valid42".parse::<TokenStart>().is_err());
+
+    let re = reformation::Regex::new(Token::regex_str()).unwrap();
+    let ids: Vec<_> = re.captures_iter("valid1 id2, id3x id4")
+        .map(|c| Token::from_captures(&c, 1).unwrap().id)
+        .collect();
+    assert_eq!(ids, vec![2, 4]);
+}
@@ -0,0 +1,268 @@
+use reformation::Reformation;
+use std::convert::TryFrom;
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Gender{
+    #[reformation("M")]
+    Male,
+    #[reformation("F")]
+    Female,
+    #[reformation("X")]
+    Other,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Sign{
+    #[reformation(r"\+")]
+    Plus,
+    #[reformation(r"-")]
+    Minus,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Direction{
+    #[reformation(r"up|north")]
+    Up,
+    Down,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{gender}{sign}{direction}")]
+struct Record{
+    gender: Gender,
+    sign: Sign,
+    direction: Direction,
+}
+
+#[test]
+fn test_enum_from_char(){
+    assert_eq!(Gender::regex_str(), "([MFX])");
+    assert_eq!("M".parse::<Gender>().unwrap(), Gender::Male);
+    assert_eq!("F".parse::<Gender>().unwrap(), Gender::Female);
+    assert_eq!("X".parse::<Gender>().unwrap(), Gender::Other);
+    assert!("Q".parse::<Gender>().is_err());
+
+    assert_eq!(Sign::regex_str(), r"([\+\-])");
+    assert_eq!("-".parse::<Sign>().unwrap(), Sign::Minus);
+}
+
+#[test]
+fn test_enum_from_formats(){
+    assert_eq!(Direction::captures_count(), 2);
+    assert_eq!("north".parse::<Direction>().unwrap(), Direction::Up);
+    assert_eq!("Down".parse::<Direction>().unwrap(), Direction::Down);
+    assert!("left".parse::<Direction>().is_err());
+    assert_eq!(Direction::min_len(), 2);
+
+    let r: Record = "F+Down".parse().unwrap();
+    assert_eq!(r, Record{gender: Gender::Female, sign: Sign::Plus, direction: Direction::Down});
+}
+
+#[derive(Reformation, Debug, PartialEq, Clone, Copy)]
+#[reformation(repr)]
+#[repr(u8)]
+enum Opcode{
+    Nop = 0,
+    Load = 0x10,
+    Store,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"op={op}")]
+struct Instruction{
+    op: Opcode,
+}
+
+#[test]
+fn test_repr_enum(){
+    assert_eq!("0".parse::<Opcode>().unwrap(), Opcode::Nop);
+    assert_eq!("16".parse::<Opcode>().unwrap(), Opcode::Load);
+    assert_eq!("op=17".parse::<Instruction>().unwrap().op, Opcode::Store);
+    assert!("5".parse::<Opcode>().is_err());
+    assert!("256".parse::<Opcode>().is_err());
+    assert_eq!(Opcode::parse_opt("17"), Some(Opcode::Store));
+    assert_eq!(Opcode::parse_opt("5"), None);
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Coords{
+    #[reformation(r"\({}\)")]
+    One(i32),
+    #[reformation(r"\({}, {}\)")]
+    Two(i32, i32),
+    #[reformation(r"\({x}, {y}, {z}\)")]
+    Three{x: i32, y: i32, z: i32},
+    #[reformation(r"\(\)")]
+    Zero,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Loose{
+    #[reformation(r"{}")]
+    One(u8),
+    #[reformation(r"{} {}")]
+    Two(u8, u8),
+}
+
+#[test]
+fn test_enum_arity(){
+    assert_eq!("()".parse::<Coords>().unwrap(), Coords::Zero);
+    assert_eq!("(1)".parse::<Coords>().unwrap(), Coords::One(1));
+    assert_eq!("(1, -2)".parse::<Coords>().unwrap(), Coords::Two(1, -2));
+    assert_eq!("(1, 2, 3)".parse::<Coords>().unwrap(), Coords::Three{x: 1, y: 2, z: 3});
+    assert!("(1, 2, 3, 4)".parse::<Coords>().is_err());
+    assert_eq!(Coords::parse_opt("(1, -2)"), Some(Coords::Two(1, -2)));
+    assert_eq!(Coords::parse_opt("(1, x)"), None);
+    assert_eq!(reformation::audit::<Coords>(), Ok(()));
+
+    // variant with more fields is tried first
+    assert_eq!("1 2".parse::<Loose>().unwrap(), Loose::Two(1, 2));
+    assert_eq!("1".parse::<Loose>().unwrap(), Loose::One(1));
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Repeat{
+    #[reformation(r"x{2}")]
+    Double,
+    #[reformation(r"y{{3}}")]
+    Triple,
+    #[reformation(r"\{{{}\}}")]
+    Braced(u8),
+}
+
+#[test]
+fn test_unit_variant_repetition(){
+    assert_eq!("xx".parse::<Repeat>().unwrap(), Repeat::Double);
+    assert_eq!("yyy".parse::<Repeat>().unwrap(), Repeat::Triple);
+    assert_eq!("{7}".parse::<Repeat>().unwrap(), Repeat::Braced(7));
+    assert!("x".parse::<Repeat>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(ignore_case_fields)]
+enum Status{
+    Active,
+    #[reformation(r"assign to {}")]
+    Assign(Gender),
+    #[reformation(r"x")]
+    Unknown,
+}
+
+#[test]
+fn test_enum_ignore_case(){
+    assert_eq!("ACTIVE".parse::<Status>().unwrap(), Status::Active);
+    assert_eq!("active".parse::<Status>().unwrap(), Status::Active);
+    assert_eq!("AcTiVe".parse::<Status>().unwrap(), Status::Active);
+    assert_eq!("X".parse::<Status>().unwrap(), Status::Unknown);
+    assert_eq!("Assign TO F".parse::<Status>().unwrap(), Status::Assign(Gender::Female));
+    // field regular expressions stay case sensitive
+    assert!("assign to f".parse::<Status>().is_err());
+    assert!("actve".parse::<Status>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Priority{
+    #[reformation("high")]
+    High,
+    #[reformation("low")]
+    Low,
+    #[reformation(empty)]
+    Normal,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{name}:{priority}$")]
+struct Task{
+    name: String,
+    priority: Priority,
+}
+
+#[test]
+fn test_empty_variant(){
+    assert_eq!("".parse::<Priority>().unwrap(), Priority::Normal);
+    assert_eq!("high".parse::<Priority>().unwrap(), Priority::High);
+    assert_eq!("low".parse::<Priority>().unwrap(), Priority::Low);
+    assert_eq!(Priority::min_len(), 0);
+
+    let t: Task = "build:".parse().unwrap();
+    assert_eq!(t, Task{name: "build".to_string(), priority: Priority::Normal});
+    let t: Task = "build:low".parse().unwrap();
+    assert_eq!(t, Task{name: "build".to_string(), priority: Priority::Low});
+    assert!("build:medium".parse::<Task>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(sequential, try_from)]
+enum Value{
+    #[reformation("{}")]
+    Int(i64),
+    #[reformation("{}")]
+    Float(f64),
+    #[reformation("if")]
+    If,
+    #[reformation("{}")]
+    Ident(String),
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+enum Alternation{
+    #[reformation("{}")]
+    Int(i64),
+    #[reformation("{}")]
+    Float(f64),
+}
+
+#[test]
+fn test_sequential(){
+    assert_eq!("42".parse::<Value>().unwrap(), Value::Int(42));
+    // in single alternation `Int` would match prefix `4`
+    assert_eq!("4.5".parse::<Value>().unwrap(), Value::Float(4.5));
+    assert_eq!("4.5".parse::<Alternation>().unwrap(), Alternation::Int(4));
+    assert_eq!("if".parse::<Value>().unwrap(), Value::If);
+    assert_eq!("iffy".parse::<Value>().unwrap(), Value::Ident("iffy".to_string()));
+    // `Int` matches, but overflows: next variant is tried
+    assert_eq!("99999999999999999999".parse::<Value>().unwrap(), Value::Float(1e20));
+    assert_eq!(Value::try_from(String::from("4.5")).unwrap(), Value::Float(4.5));
+    // `parse_opt` tries variants in the same order
+    assert_eq!(Value::parse_opt("4.5"), Some(Value::Float(4.5)));
+    assert_eq!(Value::parse_opt("99999999999999999999"), Some(Value::Float(1e20)));
+    assert_eq!(Value::parse_opt("iffy"), Some(Value::Ident("iffy".to_string())));
+    assert_eq!(Port::parse_opt("70000"), None);
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(sequential)]
+enum Token<T: Reformation>{
+    #[reformation("{}")]
+    Number(T),
+    #[reformation("{}")]
+    Word(String),
+}
+
+#[test]
+fn test_sequential_generic(){
+    assert_eq!("42".parse::<Token<u8>>().unwrap(), Token::Number(42));
+    assert_eq!("-4".parse::<Token<u8>>().unwrap(), Token::Word("-4".to_string()));
+    assert_eq!("-4".parse::<Token<i8>>().unwrap(), Token::Number(-4));
+    assert_eq!("4.5".parse::<Token<i8>>().unwrap(), Token::Word("4.5".to_string()));
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(sequential)]
+enum Port{
+    #[reformation(r"{}")]
+    Number(u16),
+    #[reformation(r"http")]
+    Http,
+}
+
+#[test]
+fn test_sequential_errors(){
+    assert_eq!("http".parse::<Port>().unwrap(), Port::Http);
+    assert_eq!("80".parse::<Port>().unwrap(), Port::Number(80));
+    let err = "70000".parse::<Port>().unwrap_err().to_string();
+    assert!(err.contains("number too large"), "{}", err);
+    // anchored: whole input must match variant
+    assert!(" 80".parse::<Port>().is_err());
+    assert!("https".parse::<Port>().is_err());
+}
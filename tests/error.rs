@@ -0,0 +1,81 @@
+use reformation::{Reformation, NoRegexMatch, ReformationError};
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{x}:{y}", error = ReformationError)]
+struct Detailed{
+    x: u8,
+    y: u8,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{x}:{y}", error = NoRegexMatch)]
+struct Lightweight{
+    x: u8,
+    y: u8,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{x}:{y}")]
+struct Boxed{
+    x: u8,
+    y: u8,
+}
+
+#[test]
+fn test_reformation_error(){
+    let p: Detailed = "1:2".parse().unwrap();
+    assert_eq!((p.x, p.y), (1, 2));
+
+    match "1-2".parse::<Detailed>(){
+        Err(ReformationError::NoRegexMatch(e)) => assert_eq!(e.request, "1-2"),
+        other => panic!("unexpected result {:?}", other),
+    }
+    match "1:300".parse::<Detailed>(){
+        Err(ReformationError::InvalidValue{request, ..}) => assert_eq!(request, "1:300"),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_no_regex_match_error(){
+    let p: Lightweight = "1:2".parse().unwrap();
+    assert_eq!((p.x, p.y), (1, 2));
+    let err: NoRegexMatch = "1-2".parse::<Lightweight>().unwrap_err();
+    assert_eq!(err.request, "1-2");
+    let err: NoRegexMatch = "1:300".parse::<Lightweight>().unwrap_err();
+    assert_eq!(err.request, "1:300");
+}
+
+#[test]
+fn test_default_error(){
+    let p: Boxed = "1:2".parse().unwrap();
+    assert_eq!((p.x, p.y), (1, 2));
+    let err = "1-2".parse::<Boxed>().unwrap_err();
+    assert!(err.downcast_ref::<NoRegexMatch>().is_some());
+    let err = "1:300".parse::<Boxed>().unwrap_err();
+    assert!(err.downcast_ref::<std::num::ParseIntError>().is_some());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^name: {name}\nsize: {width}x{height}\ndepth: {depth}$", error = NoRegexMatch)]
+struct Image{
+    name: String,
+    width: u32,
+    height: u32,
+    depth: u8,
+}
+
+#[test]
+fn test_line_column(){
+    let image: Image = "name: cat.png\nsize: 640x480\ndepth: 8".parse().unwrap();
+    assert_eq!((image.name.as_str(), image.width, image.height, image.depth), ("cat.png", 640, 480, 8));
+
+    let input = "name: cat.png\nsize: 640x480\ndepth: deep";
+    let err = input.parse::<Image>().unwrap_err();
+    assert_eq!(err.line_column(), (3, 8));
+    // position is computed only on request, not by `Display`
+    assert!(!err.to_string().contains("near line"), "{}", err);
+
+    let err = reformation::NoRegexMatchRef{format: Image::regex_str(), request: input};
+    assert_eq!(err.line_column(), (3, 8));
+}
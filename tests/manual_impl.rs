@@ -0,0 +1,93 @@
+use reformation::{Reformation, Captures};
+use std::error::Error;
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"\({x}, {y}\)")]
+struct Point{
+    x: i32,
+    y: i32,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"#{color}")]
+struct Color{
+    #[reformation(radix = 16)]
+    color: u32,
+}
+
+/// Composite written by hand: `<point> <color>`
+#[derive(Debug, PartialEq)]
+struct Pixel{
+    point: Point,
+    color: Color,
+}
+
+impl Reformation for Pixel{
+    fn regex_str()->&'static str{
+        reformation::lazy_static!{
+            static ref STR: String = format!("{} {}", Point::regex_str(), Color::regex_str());
+        }
+        &STR
+    }
+
+    fn captures_count()->usize{
+        Point::captures_count() + Color::captures_count()
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        let (point, offset) = Point::from_captures_consuming(c, offset)?;
+        let (color, _) = Color::from_captures_consuming(c, offset)?;
+        Ok(Pixel{point, color})
+    }
+}
+
+#[test]
+fn test_consuming_composition(){
+    let re = reformation::Regex::new(Pixel::regex_str()).unwrap();
+    let captures = re.captures("(3, -4) #ff00ff").unwrap();
+    let (pixel, offset) = Pixel::from_captures_consuming(&captures, 1).unwrap();
+    assert_eq!(pixel, Pixel{
+        point: Point{x: 3, y: -4},
+        color: Color{color: 0xff00ff},
+    });
+    assert_eq!(offset, 1 + Pixel::captures_count());
+    assert_eq!(offset, captures.len());
+}
+
+/// Written by hand against named groups
+#[derive(Debug, PartialEq)]
+struct Size{
+    width: u32,
+    height: u32,
+}
+
+impl Reformation for Size{
+    fn regex_str()->&'static str{
+        r"(?P<width>\d+)x(?P<height>\d+)"
+    }
+
+    fn captures_count()->usize{
+        2
+    }
+
+    fn from_captures(c: &Captures, _offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(Size{
+            width: reformation::named(c, "width")?,
+            height: reformation::named(c, "height")?,
+        })
+    }
+}
+
+#[test]
+fn test_named_groups(){
+    let re = reformation::Regex::new(Size::regex_str()).unwrap();
+    let captures = re.captures("640x480").unwrap();
+    assert_eq!(Size::from_captures(&captures, 1).unwrap(), Size{width: 640, height: 480});
+
+    let re = reformation::Regex::new(r"(?P<x>\d+)|(?P<y>[a-z]+)").unwrap();
+    let captures = re.captures("12").unwrap();
+    assert_eq!(reformation::named::<u32>(&captures, "x").unwrap(), 12);
+    assert!(reformation::named::<u32>(&captures, "y").is_err());
+    let captures = re.captures("ab").unwrap();
+    assert!(reformation::named::<u32>(&captures, "y").is_err());
+}
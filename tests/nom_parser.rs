@@ -0,0 +1,59 @@
+#![cfg(feature = "nom")]
+
+use reformation::Reformation;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, preceded};
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"\({x}, {y}\)", nom)]
+struct Point{
+    x: i32,
+    y: i32,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"(?P<name>\w+)", raw, nom)]
+struct Name{
+    name: String,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{left}..{right}", nom)]
+struct Span<T: Reformation>{
+    left: T,
+    right: T,
+}
+
+#[test]
+fn test_parse_nom(){
+    assert_eq!(Point::parse_nom("(1, 2) tail"), Ok((" tail", Point{x: 1, y: 2})));
+    assert!(Point::parse_nom("tail (1, 2)").is_err());
+    assert!(Point::parse_nom("(1, 99999999999)").is_err());
+}
+
+#[test]
+fn test_nom_composition(){
+    let mut path = delimited(tag("["), separated_list1(tag(" -> "), Point::parse_nom), tag("]"));
+    let (rest, points) = path("[(0, 0) -> (1, -1) -> (2, 4)];").unwrap();
+    assert_eq!(rest, ";");
+    assert_eq!(points, vec![Point{x: 0, y: 0}, Point{x: 1, y: -1}, Point{x: 2, y: 4}]);
+
+    let mut item = alt((
+        preceded(tag("at "), |input| Point::parse_nom(input).map(|(rest, p)| (rest, p.x))),
+        preceded(tag("len "), reformation::parse_nom::<i32>),
+    ));
+    assert_eq!(item("at (3, 4)"), Ok(("", 3)));
+    assert_eq!(item("len 7"), Ok(("", 7)));
+    assert_eq!(Name::parse_nom("abc def"), Ok((" def", Name{name: "abc".to_string()})));
+}
+
+#[test]
+fn test_parse_nom_generic(){
+    // anchored regex is cached separately for each type argument
+    assert_eq!(Span::<u8>::parse_nom("1..2 tail"), Ok((" tail", Span{left: 1, right: 2})));
+    assert_eq!(Span::<i8>::parse_nom("-1..2"), Ok(("", Span{left: -1, right: 2})));
+    assert!(Span::<u8>::parse_nom("-1..2").is_err());
+    assert_eq!(reformation::parse_nom::<Span<u8>>("3..4"), Ok(("", Span{left: 3, right: 4})));
+}
@@ -0,0 +1,381 @@
+use reformation::Reformation;
+use std::num::{NonZeroI32, NonZeroU32, NonZeroU8};
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{count}")]
+struct Count{
+    count: NonZeroU8,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"id={id}")]
+struct Hex{
+    #[reformation(radix = 16)]
+    id: NonZeroU32,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"balance: {balance}")]
+struct Balance{
+    #[reformation(accounting)]
+    balance: NonZeroI32,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{a} {b}")]
+struct Radix{
+    #[reformation(radix = 2)]
+    a: u8,
+    #[reformation(radix = 16)]
+    b: i32,
+}
+
+#[test]
+fn test_non_zero(){
+    let c: Count = "12".parse().unwrap();
+    assert_eq!(c.count.get(), 12);
+    assert!("0".parse::<Count>().is_err());
+}
+
+#[test]
+fn test_radix_non_zero(){
+    let h: Hex = "id=0x1f".parse().unwrap();
+    assert_eq!(h.id.get(), 0x1f);
+    let h: Hex = "id=1F".parse().unwrap();
+    assert_eq!(h.id.get(), 0x1f);
+    assert!("id=0x0".parse::<Hex>().is_err());
+}
+
+#[test]
+fn test_accounting_non_zero(){
+    let b: Balance = "balance: (42)".parse().unwrap();
+    assert_eq!(b.balance.get(), -42);
+    let b: Balance = "balance: 42".parse().unwrap();
+    assert_eq!(b.balance.get(), 42);
+    assert!("balance: (0)".parse::<Balance>().is_err());
+}
+
+#[test]
+fn test_radix(){
+    let r: Radix = "0b101 -0xff".parse().unwrap();
+    assert_eq!((r.a, r.b), (5, -255));
+    let r: Radix = "11 7f".parse().unwrap();
+    assert_eq!((r.a, r.b), (3, 127));
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{x};{y}")]
+struct UnicodeMinus{
+    #[reformation(unicode_minus)]
+    x: f64,
+    #[reformation(unicode_minus, accounting)]
+    y: i32,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{value}")]
+struct AccountingFloat{
+    #[reformation(accounting)]
+    value: f64,
+}
+
+#[test]
+fn test_unicode_minus(){
+    let v: UnicodeMinus = "\u{2212}0.4;\u{2212}3".parse().unwrap();
+    assert_eq!((v.x, v.y), (-0.4, -3));
+    let v: UnicodeMinus = "-0.4;(3)".parse().unwrap();
+    assert_eq!((v.x, v.y), (-0.4, -3));
+    let v: UnicodeMinus = "1e\u{2212}2;7".parse().unwrap();
+    assert_eq!((v.x, v.y), (0.01, 7));
+    // every sign of number, including one of exponent, accepts unicode minus
+    assert_eq!(UnicodeMinus::regex_str().matches("[\u{2212}\\+-]").count(), 4);
+}
+
+#[test]
+fn test_accounting_float(){
+    assert_eq!("(0.4)".parse::<AccountingFloat>().unwrap().value, -0.4);
+    assert_eq!("2.5e3".parse::<AccountingFloat>().unwrap().value, 2500.0);
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{x}; {y}$")]
+struct CommaDecimal{
+    #[reformation(decimal = ",")]
+    x: f64,
+    #[reformation(decimal = ",", unicode_minus, accounting)]
+    y: f32,
+}
+
+#[test]
+#[allow(clippy::approx_constant)]
+fn test_comma_decimal(){
+    let v: CommaDecimal = "3,14; (2,5)".parse().unwrap();
+    assert_eq!((v.x, v.y), (3.14, -2.5));
+    let v: CommaDecimal = "-1,5e2; \u{2212},25".parse().unwrap();
+    assert_eq!((v.x, v.y), (-150.0, -0.25));
+    let v: CommaDecimal = "7; 7,".parse().unwrap();
+    assert_eq!((v.x, v.y), (7.0, 7.0));
+    assert!("3.14; 1".parse::<CommaDecimal>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{value}$")]
+struct Float{
+    value: f64,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{value}$")]
+struct StrictFloat{
+    #[reformation(strict_float)]
+    value: f64,
+}
+
+#[test]
+fn test_strict_float(){
+    // default grammar follows `str::parse`
+    assert_eq!("5.e-2".parse::<Float>().unwrap().value, 0.05);
+    assert_eq!(".5".parse::<Float>().unwrap().value, 0.5);
+    assert_eq!("5.".parse::<Float>().unwrap().value, 5.0);
+
+    assert!("5.e-2".parse::<StrictFloat>().is_err());
+    assert!(".5".parse::<StrictFloat>().is_err());
+    assert!("5.".parse::<StrictFloat>().is_err());
+    assert_eq!("5.0e-2".parse::<StrictFloat>().unwrap().value, 0.05);
+    assert_eq!("-0.5".parse::<StrictFloat>().unwrap().value, -0.5);
+    assert_eq!("5".parse::<StrictFloat>().unwrap().value, 5.0);
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"{r},{g},{b}")]
+struct Rgb{
+    #[reformation(checked)]
+    r: u8,
+    #[reformation(checked, radix = 16)]
+    g: u8,
+    #[reformation(checked)]
+    b: u8,
+}
+
+#[test]
+fn test_checked_overflow(){
+    let c: Rgb = "255,ff,0".parse().unwrap();
+    assert_eq!((c.r, c.g, c.b), (255, 255, 0));
+
+    let err = "300,0,0".parse::<Rgb>().unwrap_err().to_string();
+    assert_eq!(err, "Field `r`: value 300 overflows type `u8`");
+    let err = "0,100,0".parse::<Rgb>().unwrap_err().to_string();
+    assert_eq!(err, "Field `g`: value 100 overflows type `u8`");
+    let err = "0,0,-1".parse::<Rgb>().unwrap_err().to_string();
+    assert!(!err.contains("overflow"), "{}", err);
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{a} {b}$")]
+struct Register{
+    #[reformation(radix = 16, twos_complement)]
+    a: i8,
+    #[reformation(radix = 16, twos_complement)]
+    b: i32,
+}
+
+#[test]
+fn test_twos_complement(){
+    let r: Register = "0xFF 0x7FFFFFFF".parse().unwrap();
+    assert_eq!((r.a, r.b), (-1, i32::MAX));
+    let r: Register = "0x7F ffffff85".parse().unwrap();
+    assert_eq!((r.a, r.b), (127, -123));
+    let r: Register = "80 0".parse().unwrap();
+    assert_eq!((r.a, r.b), (-128, 0));
+    assert!("0x100 0".parse::<Register>().is_err());
+    assert!("-0x1 0".parse::<Register>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{p} {t}$")]
+struct Probability{
+    #[reformation(range = "0.0..=1.0")]
+    p: f64,
+    #[reformation(range = "-273.15..")]
+    t: f32,
+}
+
+#[test]
+fn test_float_range(){
+    let v: Probability = "0.25 -10".parse().unwrap();
+    assert_eq!((v.p, v.t), (0.25, -10.0));
+    let v: Probability = "1 1e3".parse().unwrap();
+    assert_eq!((v.p, v.t), (1.0, 1000.0));
+
+    let err = "1.5 0".parse::<Probability>().unwrap_err().to_string();
+    assert_eq!(err, "Field `p` value 1.5 is out of range 0.0..=1.0");
+    let err = "0 -300".parse::<Probability>().unwrap_err().to_string();
+    assert_eq!(err, "Field `t` value -300 is out of range -273.15..");
+    assert!("-0.1 0".parse::<Probability>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{amount} / {count}$")]
+struct Grouped{
+    #[reformation(grouping = ",", decimal = ".")]
+    amount: f64,
+    #[reformation(grouping = " ")]
+    count: u64,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{amount}$")]
+struct EuropeanGrouped{
+    #[reformation(grouping = ".", decimal = ",")]
+    amount: f64,
+}
+
+#[test]
+fn test_grouping(){
+    let g: Grouped = "1,234,567.89 / 1 000 000".parse().unwrap();
+    assert_eq!((g.amount, g.count), (1234567.89, 1_000_000));
+    let g: Grouped = "-1234.5 / 999".parse().unwrap();
+    assert_eq!((g.amount, g.count), (-1234.5, 999));
+    assert!("1,23,456 / 1".parse::<Grouped>().is_err());
+    assert!("1,234 / 10 00".parse::<Grouped>().is_err());
+
+    assert_eq!("1.234.567,5".parse::<EuropeanGrouped>().unwrap().amount, 1234567.5);
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{id}(?: x{count})?$")]
+struct Stock{
+    id: u32,
+    count: Option<NonZeroU32>,
+}
+
+#[test]
+fn test_optional_non_zero(){
+    let s: Stock = "7".parse().unwrap();
+    assert_eq!((s.id, s.count), (7, None));
+    let s: Stock = "7 x12".parse().unwrap();
+    assert_eq!(s.count.map(NonZeroU32::get), Some(12));
+    let err = "7 x0".parse::<Stock>().unwrap_err().to_string();
+    assert!(err.contains("zero"), "{}", err);
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^id={id} parent={parent}$")]
+struct BigId{
+    #[reformation(digits = "19..=39")]
+    id: u128,
+    #[reformation(digits = "..=39")]
+    parent: i128,
+}
+
+#[test]
+fn test_digits(){
+    let id: BigId = "id=12345678901234567890123456789012345678 parent=-1".parse().unwrap();
+    assert_eq!(id.id, 12345678901234567890123456789012345678);
+    assert_eq!(id.parent, -1);
+
+    let err = "id=1234567890123456789012345678901234567890 parent=0".parse::<BigId>().unwrap_err().to_string();
+    assert!(err.contains("Field `id` value 1234567890123456789012345678901234567890 has 40 digits, expected 19..=39"), "{}", err);
+    assert!("id=12345 parent=0".parse::<BigId>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{price} {balance} {total}$")]
+struct Money{
+    #[reformation(scale = 2)]
+    price: u64,
+    #[reformation(scale = 2, decimal = ",", accounting)]
+    balance: i64,
+    #[reformation(scale = 1, grouping = ",")]
+    total: i32,
+}
+
+#[test]
+fn test_scale(){
+    let m: Money = "3.14 (2,5) 1,234.5".parse().unwrap();
+    assert_eq!((m.price, m.balance, m.total), (314, -250, 12345));
+    let m: Money = "3.1 -0,01 7".parse().unwrap();
+    assert_eq!((m.price, m.balance, m.total), (310, -1, 70));
+    let m: Money = "3 ,5 2.".parse().unwrap();
+    assert_eq!((m.price, m.balance, m.total), (300, 50, 20));
+
+    let err = "3.145 0 0".parse::<Money>().unwrap_err().to_string();
+    assert!(err.contains("Value 3.145 has more than 2 fractional digits"), "{}", err);
+    assert!("-3.14 0 0".parse::<Money>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{a} {b} {c} {d}$")]
+struct Clamped{
+    #[reformation(clamp)]
+    a: u8,
+    #[reformation(accounting, radix = 16, clamp)]
+    b: i8,
+    #[reformation(accounting, checked, radix = 16)]
+    c: i16,
+    #[reformation(clamp, grouping = ",")]
+    d: NonZeroU8,
+}
+
+#[test]
+fn test_clamp(){
+    let v: Clamped = "300 (0x7f) (ff) 1,000".parse().unwrap();
+    assert_eq!((v.a, v.b, v.c, v.d.get()), (255, -127, -255, 255));
+    let v: Clamped = "7 (0xff) 7f 12".parse().unwrap();
+    assert_eq!((v.a, v.b, v.c, v.d.get()), (7, -128, 127, 12));
+    let v: Clamped = "0 -0x1000 +0x10 1".parse().unwrap();
+    assert_eq!((v.a, v.b, v.c), (0, -128, 16));
+    let v: Clamped = "0 0xffff 0 1".parse().unwrap();
+    assert_eq!(v.b, 127);
+
+    let err = "0 0 (0x8001) 1".parse::<Clamped>().unwrap_err().to_string();
+    assert_eq!(err, "Field `c`: value (0x8001) overflows type `i16`");
+    // zero is not overflow, and is not clamped
+    assert!("0 0 0 0".parse::<Clamped>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{x};{y}$")]
+struct MixedLocale{
+    #[reformation(auto_decimal)]
+    x: f64,
+    #[reformation(auto_decimal, accounting)]
+    y: f32,
+}
+
+#[test]
+#[allow(clippy::approx_constant)]
+fn test_auto_decimal(){
+    let v: MixedLocale = "3.14;(2,5)".parse().unwrap();
+    assert_eq!((v.x, v.y), (3.14, -2.5));
+    let v: MixedLocale = "3,14;-1.5e2".parse().unwrap();
+    assert_eq!((v.x, v.y), (3.14, -150.0));
+    let v: MixedLocale = ",5;7".parse().unwrap();
+    assert_eq!((v.x, v.y), (0.5, 7.0));
+
+    let err = "3,1.4;0".parse::<MixedLocale>().unwrap_err().to_string();
+    assert_eq!(err, "Value 3,1.4 contains both `.` and `,`, so decimal separator is ambiguous");
+    assert!("3,1,4;0".parse::<MixedLocale>().is_err());
+    assert!("3;,".parse::<MixedLocale>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{float} {comma} {grouped} {count}$")]
+struct PaddedNumbers{
+    #[reformation(allow_leading_zeros)]
+    float: f64,
+    #[reformation(allow_leading_zeros, decimal = ",")]
+    comma: f64,
+    #[reformation(allow_leading_zeros, grouping = ",")]
+    grouped: u32,
+    #[reformation(allow_leading_zeros)]
+    count: u8,
+}
+
+#[test]
+fn test_leading_zeros_keep_fraction(){
+    let n: PaddedNumbers = "007.05 007,05 001,005 000".parse().unwrap();
+    assert_eq!((n.float, n.comma, n.grouped, n.count), (7.05, 7.05, 1005, 0));
+    let n: PaddedNumbers = "-0.001 0,5 1,000 010".parse().unwrap();
+    assert_eq!((n.float, n.comma, n.grouped, n.count), (-0.001, 0.5, 1000, 10));
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "serde")]
+
+use reformation::Reformation;
+use serde::Deserialize;
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{year}-{month}-{day}")]
+struct Date{
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+#[derive(Deserialize, Debug)]
+struct Event{
+    name: String,
+    #[serde(deserialize_with = "reformation::deserialize")]
+    date: Date,
+}
+
+#[test]
+fn test_deserialize(){
+    let event: Event = serde_json::from_str(r#"{"name": "release", "date": "2018-12-22"}"#).unwrap();
+    assert_eq!(event.name, "release");
+    assert_eq!(event.date, Date{year: 2018, month: 12, day: 22});
+
+    let err = serde_json::from_str::<Event>(r#"{"name": "release", "date": "yesterday"}"#).unwrap_err();
+    assert!(err.to_string().contains("does not match format"), "{}", err);
+}
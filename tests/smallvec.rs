@@ -0,0 +1,28 @@
+#![cfg(feature = "smallvec")]
+
+use reformation::Reformation;
+use smallvec::SmallVec;
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^\[{items}\]$")]
+struct Small{
+    #[reformation(separator = ", ")]
+    items: SmallVec<[u32; 4]>,
+}
+
+#[test]
+fn test_inline(){
+    let s: Small = "[1, 2, 3]".parse().unwrap();
+    assert_eq!(s.items.as_slice(), &[1, 2, 3]);
+    assert!(!s.items.spilled());
+    let s: Small = "[]".parse().unwrap();
+    assert!(s.items.is_empty());
+}
+
+#[test]
+fn test_spilled(){
+    let s: Small = "[1, 2, 3, 4, 5, 6]".parse().unwrap();
+    assert_eq!(s.items.as_slice(), &[1, 2, 3, 4, 5, 6]);
+    assert!(s.items.spilled());
+    assert!("[1, x]".parse::<Small>().is_err());
+}
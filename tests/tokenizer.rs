@@ -0,0 +1,41 @@
+use reformation::{Reformation, Captures, Tokenizer};
+use std::error::Error;
+
+#[derive(Debug, PartialEq)]
+struct Word(String);
+
+impl Reformation for Word{
+    fn regex_str()->&'static str{
+        r"(\w+)"
+    }
+
+    fn captures_count()->usize{
+        1
+    }
+
+    fn from_captures(c: &Captures, offset: usize)->Result<Self, Box<dyn Error>>{
+        Ok(Word(c.get(offset).unwrap().as_str().to_string()))
+    }
+}
+
+#[test]
+fn test_tokenize_sequence(){
+    let mut tokens = Tokenizer::new("12 foo 3.5");
+    assert_eq!(tokens.next::<i32>().unwrap(), 12);
+    assert_eq!(tokens.next::<Word>().unwrap(), Word("foo".to_string()));
+    assert_eq!(tokens.next::<f64>().unwrap(), 3.5);
+    assert!(tokens.is_empty());
+    assert!(tokens.next::<i32>().is_err());
+}
+
+#[test]
+fn test_scan(){
+    let (n, w) = reformation::scan!(" 12 foo ", i32, Word).unwrap();
+    assert_eq!((n, w), (12, Word("foo".to_string())));
+    assert_eq!(reformation::scan!("12", i32).unwrap(), 12);
+
+    let err = reformation::scan!("12 foo bar", i32, Word).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected input at position 6: \" bar\"");
+    let err = reformation::scan!("x", i32).unwrap_err();
+    assert!(err.to_string().starts_with("Cannot scan `i32` at position 0"), "{}", err);
+}
@@ -0,0 +1,668 @@
+use reformation::{Reformation, TriState, Regex, AsciiChar, ClockTime, MacAddr, Sign, CompoundDuration, ByteSize, SignedDuration, Percent, Ip4Parsed, Ip6Parsed, PathComponents};
+use std::error::Error;
+use std::cmp::Reverse;
+use std::ffi::CString;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::Wrapping;
+use std::ops::Bound;
+use std::time::Duration;
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^\[{value}\]$")]
+struct Bracketed{
+    value: Option<String>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^\[(?:{value})?\]$")]
+struct OptionalBracketed{
+    value: Option<String>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^\[{value}\]$")]
+struct Number{
+    value: Option<i32>,
+}
+
+#[test]
+fn test_option_empty_match(){
+    // `(.*)` participates in match even if it matches empty string
+    assert_eq!("[]".parse::<Bracketed>().unwrap().value, Some(String::new()));
+    assert_eq!("[a]".parse::<Bracketed>().unwrap().value, Some("a".to_string()));
+}
+
+#[test]
+fn test_option_no_group(){
+    // optional group is free to be skipped
+    assert_eq!("[]".parse::<OptionalBracketed>().unwrap().value, Some(String::new()));
+    assert_eq!("[]".parse::<Number>().unwrap().value, None);
+    assert_eq!("[-3]".parse::<Number>().unwrap().value, Some(-3));
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{a} -> {b}")]
+struct Segment{
+    a: (i32, i32),
+    b: (i32, i32),
+}
+
+#[test]
+fn test_tuple(){
+    let s: Segment = "(1, 2) -> (-3,4)".parse().unwrap();
+    assert_eq!(s, Segment{a: (1, 2), b: (-3, 4)});
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"{x}:{y}")]
+struct Point{
+    x: i32,
+    y: i32,
+}
+
+reformation::create_parse_fn!{parse_line, r"^{}\s+{}\s+{}$", Point, Option<u8>, String}
+
+#[test]
+fn test_create_parse_fn_nested(){
+    let (point, n, s) = parse_line("1:-2 3 abc").unwrap();
+    assert_eq!((point, n, s.as_str()), (Point{x: 1, y: -2}, Some(3), "abc"));
+}
+
+reformation::create_parse_fn!{
+    parse_many, r"^{} {} {} {} {} {} {} {} {} {} {} {} {}$",
+    u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, i8
+}
+
+#[test]
+fn test_create_parse_fn_many(){
+    // more types than tuples implementing `Reformation`
+    let values = parse_many("1 2 3 4 5 6 7 8 9 10 11 12 -13").unwrap();
+    assert_eq!((values.0, values.11, values.12), (1, 12, -13));
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^lines {lines}$")]
+struct Lines{
+    lines: std::ops::Range<u32>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{from}; {to}$")]
+struct OpenRanges{
+    from: std::ops::RangeFrom<i32>,
+    to: std::ops::RangeTo<i32>,
+}
+
+#[test]
+fn test_ranges(){
+    assert_eq!("lines 3..7".parse::<Lines>().unwrap().lines, 3..7);
+    let r: OpenRanges = "3..; ..7".parse().unwrap();
+    assert_eq!(r, OpenRanges{from: 3.., to: ..7});
+    let r: OpenRanges = "-3..; ..-7".parse().unwrap();
+    assert_eq!(r, OpenRanges{from: -3.., to: ..-7});
+    assert!("..3; ..7".parse::<OpenRanges>().is_err());
+    assert!("3..; 7..".parse::<OpenRanges>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{start}, {end}\)$")]
+struct Interval{
+    start: Bound<i32>,
+    end: i32,
+}
+
+#[test]
+fn test_bound(){
+    assert_eq!("[3, 7)".parse::<Interval>().unwrap(), Interval{start: Bound::Included(3), end: 7});
+    assert_eq!("(-3, 7)".parse::<Interval>().unwrap(), Interval{start: Bound::Excluded(-3), end: 7});
+    assert_eq!("(-inf, 7)".parse::<Interval>().unwrap().start, Bound::Unbounded);
+    assert_eq!("(∞, 7)".parse::<Interval>().unwrap().start, Bound::Unbounded);
+    assert_eq!("(, 7)".parse::<Interval>().unwrap().start, Bound::Unbounded);
+    assert!("[-inf, 7)".parse::<Interval>().is_err());
+    assert!("[, 7)".parse::<Interval>().is_err());
+    assert!("3, 7)".parse::<Interval>().is_err());
+    assert!("{3, 7)".parse::<Interval>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{address}(?::{port})?$")]
+struct Host{
+    address: (u8, u8),
+    #[reformation(or = "80")]
+    port: Option<u16>,
+}
+
+#[test]
+fn test_option_or(){
+    let h: Host = "(1, 2)".parse().unwrap();
+    assert_eq!(h, Host{address: (1, 2), port: Some(80)});
+    let h: Host = "(1, 2):8080".parse().unwrap();
+    assert_eq!(h, Host{address: (1, 2), port: Some(8080)});
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^<{v}>$")]
+struct Generic<T>{
+    v: Option<T>,
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{items}$")]
+struct GenericList<T: std::hash::Hash + Eq>{
+    #[reformation(separator = ",")]
+    items: std::collections::HashSet<T>,
+}
+
+#[test]
+fn test_generic_option(){
+    assert_eq!("<12>".parse::<Generic<u32>>().unwrap(), Generic{v: Some(12)});
+    assert_eq!("<>".parse::<Generic<u32>>().unwrap(), Generic{v: None});
+    // every instantiation has its own regular expression
+    assert!("<-1>".parse::<Generic<u32>>().is_err());
+    assert_eq!("<-1>".parse::<Generic<i8>>().unwrap(), Generic{v: Some(-1)});
+    assert_ne!(Generic::<u32>::regex_str(), Generic::<i8>::regex_str());
+
+    let list: GenericList<u8> = "1,2,1".parse().unwrap();
+    assert_eq!(list.items.len(), 2);
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^<{v}>$", canonical)]
+struct Rendered<T: std::fmt::Display>{
+    v: T,
+}
+
+#[test]
+fn test_generic_canonical(){
+    assert_eq!("<7>".parse::<Rendered<u8>>().unwrap().canonical(), "<7>");
+    // rendering does not require type parameter to be `'static`
+    let text = String::from("text");
+    assert_eq!(Rendered{v: text.as_str()}.canonical(), "<text>");
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^name: {name}(?:, nick: {nick})?$")]
+struct Name{
+    #[reformation(max_len = 5)]
+    name: String,
+    #[reformation(max_len = 3)]
+    nick: Option<String>,
+}
+
+#[test]
+fn test_max_len(){
+    assert_eq!("name: Alice".parse::<Name>().unwrap().name, "Alice");
+    assert_eq!("name: ".parse::<Name>().unwrap().name, "");
+    assert_eq!("name: Ёжик".parse::<Name>().unwrap().name, "Ёжик");
+    assert!("name: Alexander".parse::<Name>().is_err());
+    let name: Name = "name: Alice, nick: Al".parse().unwrap();
+    assert_eq!((name.name.as_str(), name.nick.as_deref()), ("Alice", Some("Al")));
+    assert_eq!("name: Bob".parse::<Name>().unwrap().nick, None);
+    assert!("name: Alice, nick: Ally".parse::<Name>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^timeout={timeout}$")]
+struct Timeout{
+    timeout: std::time::Duration,
+}
+
+#[test]
+fn test_duration(){
+    use std::time::Duration;
+
+    let parse = |s: &str| s.parse::<Timeout>().map(|t| t.timeout);
+    assert_eq!(parse("timeout=1.5s").unwrap(), Duration::from_millis(1500));
+    assert_eq!(parse("timeout=0.25h").unwrap(), Duration::from_secs(900));
+    assert_eq!(parse("timeout=2.25h").unwrap(), Duration::from_secs(8100));
+    assert_eq!(parse("timeout=250ms").unwrap(), Duration::from_millis(250));
+    assert_eq!(parse("timeout=3 m").unwrap(), Duration::from_secs(180));
+    assert_eq!(parse("timeout=1.5us").unwrap(), Duration::from_nanos(1500));
+    assert!(parse("timeout=-1s").is_err());
+    assert!(parse("timeout=1e400s").is_err());
+    assert!(parse("timeout=99999999999999999999d").is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{src} -> {dst}$")]
+struct Route{
+    src: Ipv4Addr,
+    #[reformation(allow_leading_zeros)]
+    dst: Ipv4Addr,
+}
+
+#[test]
+fn test_ipv4_leading_zeros(){
+    let r: Route = "10.0.0.1 -> 010.000.001.001".parse().unwrap();
+    assert_eq!(r.src, Ipv4Addr::new(10, 0, 0, 1));
+    assert_eq!(r.dst, Ipv4Addr::new(10, 0, 1, 1));
+    assert!("010.0.0.1 -> 10.0.0.1".parse::<Route>().is_err());
+    assert!("10.0.0.1 -> 256.0.0.1".parse::<Route>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{v4} {v6}$")]
+struct Interface{
+    v4: reformation::Ip4Parsed,
+    v6: reformation::Ip6Parsed,
+}
+
+#[test]
+fn test_ip_octets(){
+    let i: Interface = "192.168.1.20 fe80::1ff:fe23:4567:890a".parse().unwrap();
+    assert_eq!(i.v4.addr, Ipv4Addr::new(192, 168, 1, 20));
+    assert_eq!(i.v4.octets, i.v4.addr.octets());
+    assert_eq!(i.v6.addr, "fe80::1ff:fe23:4567:890a".parse::<Ipv6Addr>().unwrap());
+    assert_eq!(i.v6.segments, [0xfe80, 0, 0, 0, 0x1ff, 0xfe23, 0x4567, 0x890a]);
+    assert!("192.168.1.20 fe80:::1".parse::<Interface>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{priority} {counter}$")]
+struct Job{
+    priority: Reverse<u32>,
+    counter: Wrapping<u8>,
+}
+
+#[test]
+fn test_transparent_wrappers(){
+    let job: Job = "5 255".parse().unwrap();
+    assert_eq!(job.priority, Reverse(5));
+    assert_eq!(job.counter + Wrapping(1), Wrapping(0));
+    assert!("-5 0".parse::<Job>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{id}: {name}$")]
+struct Label{
+    id: u32,
+    name: Box<str>,
+}
+
+#[test]
+fn test_boxed_str(){
+    let label: Label = "3: first label".parse().unwrap();
+    assert_eq!(label.id, 3);
+    assert_eq!(&*label.name, "first label");
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^open {path}$")]
+struct Open{
+    path: CString,
+}
+
+#[test]
+fn test_c_string(){
+    let open: Open = "open /tmp/file name".parse().unwrap();
+    assert_eq!(open.path, CString::new("/tmp/file name").unwrap());
+    assert_eq!(open.path.as_bytes().len(), 14);
+    assert!("open /tmp\0file".parse::<Open>().is_err());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"\({left}, {right}\)")]
+struct Pair<T>{
+    left: Box<T>,
+    right: Box<T>,
+}
+
+#[test]
+fn test_boxed_nesting(){
+    let p: Pair<Pair<u8>> = "((1, 2), (3, 4))".parse().unwrap();
+    let leaf = |a, b| Box::new(Pair{left: Box::new(a), right: Box::new(b)});
+    assert_eq!(p, Pair{left: leaf(1, 2), right: leaf(3, 4)});
+    assert!("((1, 2), 3)".parse::<Pair<Pair<u8>>>().is_err());
+}
+
+reformation::literal!{Arrow = "->"}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{from}{arrow}{to}$")]
+struct Edge{
+    from: u32,
+    arrow: Option<Arrow>,
+    to: u32,
+}
+
+#[test]
+fn test_literal(){
+    assert_eq!(Arrow::captures_count(), 0);
+    let e: Edge = "1->2".parse().unwrap();
+    assert_eq!((e.from, e.arrow, e.to), (1, Some(Arrow), 2));
+    let e: Edge = "12".parse().unwrap();
+    assert_eq!(e.arrow, None);
+    assert!("1=>2".parse::<Edge>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{value} {sign}{error}$")]
+struct Measurement{
+    value: f64,
+    sign: reformation::Sign,
+    error: f64,
+}
+
+#[test]
+fn test_sign_field(){
+    let m: Measurement = "9.81 ±3.2".parse().unwrap();
+    assert_eq!((m.value, m.sign, m.error), (9.81, reformation::Sign::PlusMinus, 3.2));
+    let m: Measurement = "1 -0.5".parse().unwrap();
+    assert_eq!(m.sign.apply(m.error), -0.5);
+    assert!("1 0.5".parse::<Measurement>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{sensor}={reading};$")]
+struct Reading{
+    sensor: u8,
+    reading: Result<f32, String>,
+}
+
+#[test]
+fn test_result(){
+    let r: Reading = "3=21.5;".parse().unwrap();
+    assert_eq!((r.sensor, r.reading), (3, Ok(21.5)));
+    let r: Reading = "4=ERR_TIMEOUT;".parse().unwrap();
+    assert_eq!((r.sensor, r.reading), (4, Err("ERR_TIMEOUT".to_string())));
+    assert_eq!(reformation::audit::<Reading>(), Ok(()));
+}
+
+reformation::create_parse_type!{#[derive(Debug, PartialEq)] Version, r"v{}\.{}", u8, u8}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{package}@{version}$")]
+struct Dependency{
+    package: String,
+    version: Version,
+}
+
+#[test]
+fn test_create_parse_type(){
+    let d: Dependency = "regex@v1.13".parse().unwrap();
+    assert_eq!(d.package, "regex");
+    assert_eq!(d.version, Version((1, 13)));
+    assert!("regex@1.13".parse::<Dependency>().is_err());
+    assert_eq!(reformation::audit::<Version>(), Ok(()));
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{v4} {v6}$")]
+struct Listen{
+    v4: SocketAddrV4,
+    v6: SocketAddrV6,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^connect {addr}$")]
+struct Connect{
+    addr: SocketAddr,
+}
+
+#[test]
+fn test_socket_addr(){
+    let l: Listen = "127.0.0.1:8080 [::1]:443".parse().unwrap();
+    assert_eq!(l.v4, SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080));
+    assert_eq!(l.v6, SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 0, 0));
+    let l: Listen = "0.0.0.0:1 [fe80::1%2]:22".parse().unwrap();
+    assert_eq!(l.v6.scope_id(), 2);
+
+    // address families are not interchangeable
+    assert!("[::1]:80 [::1]:443".parse::<Listen>().is_err());
+    assert!("127.0.0.1:80 127.0.0.1:443".parse::<Listen>().is_err());
+    assert!("127.0.0.1:70000 [::1]:443".parse::<Listen>().is_err());
+
+    let c: Connect = "connect 10.0.0.1:22".parse().unwrap();
+    assert!(c.addr.is_ipv4());
+    let c: Connect = "connect [::1]:22".parse().unwrap();
+    assert!(c.addr.is_ipv6());
+}
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"\({left},{right}\)")]
+struct Tag<T>{
+    left: T,
+    right: T,
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{id}(?: -> {next})?;(?: {weight})?;(?: {tags})?$")]
+struct Node{
+    id: u32,
+    next: Option<Box<Pair<u8>>>,
+    weight: Box<Option<i32>>,
+    #[reformation(separator = " ")]
+    #[allow(clippy::vec_box)]
+    tags: Vec<Box<Tag<u8>>>,
+}
+
+#[test]
+fn test_nested_wrappers(){
+    assert_eq!(<Option<Box<Pair<u8>>>>::captures_count(), 3);
+    assert_eq!(<Box<Option<i32>>>::captures_count(), 2);
+    assert_eq!(<Box<Option<Box<u8>>>>::captures_count(), 2);
+    assert_eq!(<Box<Box<Pair<u8>>>>::min_len(), Pair::<u8>::min_len());
+    assert_eq!(<Option<Box<u8>>>::min_len(), 0);
+
+    let n: Node = "1 -> (2, 3); -4; (5,6) (7,8)".parse().unwrap();
+    assert_eq!(n.next, Some(Box::new(Pair{left: Box::new(2), right: Box::new(3)})));
+    assert_eq!(*n.weight, Some(-4));
+    assert_eq!(n.tags.len(), 2);
+    assert_eq!(*n.tags[1], Tag{left: 7, right: 8});
+
+    let n: Node = "1;;".parse().unwrap();
+    assert_eq!((n.id, n.next), (1, None));
+    assert_eq!(*n.weight, None);
+    assert!(n.tags.is_empty());
+    assert!("1 -> (2, 300);;".parse::<Node>().is_err());
+}
+
+reformation::tri_state!{Checked = "[x]", "[ ]", "[?]"}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{done} {task} \(tested: {tested}\)$")]
+struct Todo{
+    done: Checked,
+    task: String,
+    tested: TriState,
+}
+
+#[test]
+fn test_tri_state(){
+    let t: Todo = "[x] write docs (tested: true)".parse().unwrap();
+    assert_eq!((t.done.0, t.task.as_str(), t.tested), (TriState::True, "write docs", TriState::True));
+    let t: Todo = "[ ] release (tested: false)".parse().unwrap();
+    assert_eq!((t.done.0, t.tested), (TriState::False, TriState::False));
+    let t: Todo = "[?] triage (tested: unknown)".parse().unwrap();
+    assert_eq!((t.done.0, t.tested.to_option()), (TriState::Unknown, None));
+
+    assert!("[-] triage (tested: true)".parse::<Todo>().is_err());
+    assert!("[x] triage (tested: yes)".parse::<Todo>().is_err());
+    assert!("[x] triage (tested: )".parse::<Todo>().is_err());
+}
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^{time} UTC{zone}$")]
+struct Timestamp{
+    time: reformation::ClockTime,
+    zone: reformation::SignedDuration,
+}
+
+#[test]
+fn test_signed_duration(){
+    let t: Timestamp = "12:00:00 UTC+01:00".parse().unwrap();
+    assert_eq!(t.zone.0, 3600);
+    let t: Timestamp = "12:00:00 UTC-05:30".parse().unwrap();
+    assert_eq!(t.zone.0, -(5 * 3600 + 30 * 60));
+    let t: Timestamp = "23:59:59 UTC00:00".parse().unwrap();
+    assert_eq!((t.time.0.as_secs(), t.zone.0), (86399, 0));
+    assert!("12:00:00 UTC+1:00".parse::<Timestamp>().is_err());
+}
+
+type Load = reformation::Percent;
+
+#[derive(Reformation, Debug)]
+#[reformation(r"^cpu {cpu}, disk {disk}(?:, load {load})?$")]
+struct Usage{
+    cpu: reformation::Percent,
+    #[reformation(range = "0..=200")]
+    disk: reformation::Percent,
+    // bounds are replaced regardless of how type is named
+    #[reformation(range = "0..", default_if_absent)]
+    load: Load,
+}
+
+#[test]
+fn test_percent(){
+    let u: Usage = "cpu 50%, disk 150%".parse().unwrap();
+    assert_eq!(u.cpu.percentage(), 50.0);
+    assert_eq!(u.cpu.fraction(), 0.5);
+    assert_eq!(u.disk.fraction(), 1.5);
+    assert!("cpu 150%, disk 50%".parse::<Usage>().is_err());
+    let e = "cpu 50%, disk 250%".parse::<Usage>().unwrap_err();
+    assert!(e.to_string().contains("Field `disk` value 250% is out of range 0..=200"), "{}", e);
+    let u: Usage = "cpu 50%, disk 50%, load 400%".parse().unwrap();
+    assert_eq!(u.load.percentage(), 400.0);
+}
+
+fn parse<T: Reformation>(s: &str)->Result<T, Box<dyn Error>>{
+    let re = Regex::new(&format!("^{}$", T::regex_str())).unwrap();
+    let c = re.captures(s).ok_or("no match")?;
+    T::from_captures(&c, 1)
+}
+
+#[test]
+fn test_ascii_char(){
+    assert_eq!(parse::<AsciiChar>("a").unwrap(), AsciiChar(b'a'));
+    assert_eq!(parse::<AsciiChar>("\n").unwrap(), AsciiChar(b'\n'));
+    assert!(parse::<AsciiChar>("é").is_err());
+    assert!(parse::<AsciiChar>("ab").is_err());
+}
+
+#[test]
+fn test_clock_time(){
+    let t: ClockTime = parse("20:23:05").unwrap();
+    assert_eq!(t.0, Duration::from_secs(20 * 3600 + 23 * 60 + 5));
+    let t: ClockTime = parse("20:23:05.500").unwrap();
+    assert_eq!(t.0, Duration::from_millis((20 * 3600 + 23 * 60 + 5) * 1000 + 500));
+    assert!(parse::<ClockTime>("25:00:00").is_err());
+    assert!(parse::<ClockTime>("23:60:00").is_err());
+    assert!(parse::<ClockTime>("2:00:00").is_err());
+}
+
+#[test]
+fn test_mac_addr(){
+    let expected = MacAddr([0x01, 0x23, 0x45, 0x67, 0x89, 0xab]);
+    assert_eq!(parse::<MacAddr>("01:23:45:67:89:ab").unwrap(), expected);
+    assert_eq!(parse::<MacAddr>("01-23-45-67-89-AB").unwrap(), expected);
+    assert!(parse::<MacAddr>("01:23:45-67:89:ab").is_err());
+    assert!(parse::<MacAddr>("01:23:45:67:89").is_err());
+}
+
+#[test]
+fn test_sign(){
+    assert_eq!(parse::<Sign>("+").unwrap(), Sign::Plus);
+    assert_eq!(parse::<Sign>("-").unwrap(), Sign::Minus);
+    assert_eq!(parse::<Sign>("\u{2212}").unwrap(), Sign::Minus);
+    assert_eq!(parse::<Sign>("±").unwrap(), Sign::PlusMinus);
+    assert!(parse::<Sign>("").is_err());
+    assert_eq!(Sign::Minus.apply(3), -3);
+    assert_eq!(Sign::PlusMinus.apply(3), 3);
+}
+
+#[test]
+fn test_compound_duration(){
+    let d = |s| parse::<CompoundDuration>(s).map(|d| d.0);
+    assert_eq!(d("1h30m").unwrap(), Duration::from_secs(5400));
+    assert_eq!(d("90m").unwrap(), Duration::from_secs(5400));
+    assert_eq!(d("45s").unwrap(), Duration::from_secs(45));
+    assert_eq!(d("1d2h3m4s5ms6us7ns").unwrap(), Duration::new(93784, 5_006_007));
+    assert_eq!(d("1s500µs").unwrap(), Duration::new(1, 500_000));
+    assert!(d("30m1h").is_err());
+    assert!(d("1m1m").is_err());
+    assert!(d("1us1µs").is_err());
+    assert!(d("1.5h").is_err());
+    assert!(d("h").is_err());
+}
+
+#[test]
+fn test_byte_size(){
+    assert_eq!(parse::<ByteSize>("10MB").unwrap(), ByteSize(10_000_000));
+    assert_eq!(parse::<ByteSize>("512KiB").unwrap(), ByteSize(524_288));
+    assert_eq!(parse::<ByteSize>("3GiB").unwrap(), ByteSize(3 << 30));
+    assert_eq!(parse::<ByteSize>("64B").unwrap(), ByteSize(64));
+    assert!(parse::<ByteSize>("10XB").is_err());
+    assert!(parse::<ByteSize>("10kb").is_err());
+    assert!(parse::<ByteSize>("10").is_err());
+    assert!(parse::<ByteSize>("99999999999999999999GB").is_err());
+}
+
+#[test]
+fn test_signed_duration_bounds(){
+    assert_eq!(parse::<SignedDuration>("+01:00").unwrap(), SignedDuration(3600));
+    assert_eq!(parse::<SignedDuration>("-05:30").unwrap(), SignedDuration(-19800));
+    assert_eq!(parse::<SignedDuration>("\u{2212}05:30").unwrap(), SignedDuration(-19800));
+    assert_eq!(parse::<SignedDuration>("00:00").unwrap(), SignedDuration(0));
+    assert_eq!(parse::<SignedDuration>("-00:00:45").unwrap(), SignedDuration(-45));
+    assert_eq!(parse::<SignedDuration>("100:00").unwrap(), SignedDuration(360_000));
+    assert!(parse::<SignedDuration>("01:60").is_err());
+    assert!(parse::<SignedDuration>("1:00").is_err());
+    assert!(parse::<SignedDuration>("+0100").is_err());
+    assert!(parse::<SignedDuration>("99999999999999999999:00").is_err());
+}
+
+#[test]
+fn test_percent_bounds(){
+    assert_eq!(parse::<Percent>("50%").unwrap(), Percent(50.0));
+    assert_eq!(parse::<Percent>("12.5%").unwrap().fraction(), 0.125);
+    assert_eq!(parse::<Percent>("100%").unwrap().percentage(), 100.0);
+    assert_eq!(parse::<Percent>(".5%").unwrap(), Percent(0.5));
+    assert!(parse::<Percent>("150%").is_err());
+    assert!(parse::<Percent>("-1%").is_err());
+    assert!(parse::<Percent>("50").is_err());
+}
+
+#[test]
+fn test_tri_state_tokens(){
+    assert_eq!(parse::<TriState>("true").unwrap(), TriState::True);
+    assert_eq!(parse::<TriState>("false").unwrap(), TriState::False);
+    assert_eq!(parse::<TriState>("unknown").unwrap(), TriState::Unknown);
+    assert!(parse::<TriState>("True").is_err());
+    assert!(parse::<TriState>("maybe").is_err());
+    assert!(parse::<TriState>("").is_err());
+
+    reformation::tri_state!{Bit = "1", "0", "10"}
+    assert_eq!(TriState::tokens_regex_str(["1", "0", "10"]), "(10|1|0)");
+    assert_eq!(parse::<Bit>("1").unwrap(), Bit(TriState::True));
+    assert_eq!(parse::<Bit>("10").unwrap(), Bit(TriState::Unknown));
+    assert!(parse::<Bit>("true").is_err());
+}
+
+#[test]
+fn test_ip_parsed(){
+    let ip: Ip4Parsed = parse("10.0.255.1").unwrap();
+    assert_eq!(ip.octets, [10, 0, 255, 1]);
+    assert_eq!(ip.octets, ip.addr.octets());
+    assert!(parse::<Ip4Parsed>("10.0.256.1").is_err());
+
+    let ip: Ip6Parsed = parse("2001:db8::ff00:42:8329").unwrap();
+    assert_eq!(ip.segments, [0x2001, 0xdb8, 0, 0, 0, 0xff00, 0x42, 0x8329]);
+    assert_eq!(ip.segments, ip.addr.segments());
+    let ip: Ip6Parsed = parse("::ffff:10.0.0.1").unwrap();
+    assert_eq!(ip.segments, [0, 0, 0, 0, 0, 0xffff, 0x0a00, 0x0001]);
+    assert!(parse::<Ip6Parsed>("1:::2").is_err());
+}
+
+#[test]
+fn test_path_components(){
+    let p: PathComponents = parse("a/b/c").unwrap();
+    assert!(!p.absolute);
+    assert_eq!(p.components, vec!["a", "b", "c"]);
+    let p: PathComponents = parse("/a/b/").unwrap();
+    assert!(p.absolute);
+    assert_eq!(p.components, vec!["a", "b"]);
+    let p: PathComponents = parse("/").unwrap();
+    assert!(p.absolute && p.components.is_empty());
+    assert!(parse::<PathComponents>("a//b").is_err());
+    assert!(parse::<PathComponents>("a b").is_err());
+}
@@ -0,0 +1,17 @@
+// Compiler diagnostics differ between rustc versions, so ui tests are opt-in:
+// run them with `cargo test --test ui -- --ignored` and refresh snapshots
+// with `TRYBUILD=overwrite` after toolchain update.
+#[test]
+#[ignore = "compiler diagnostics depend on rustc version, run with --ignored"]
+fn ui(){
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/duplicate_placeholder_reuse.rs");
+    t.pass("tests/ui/radix_accounting.rs");
+    t.compile_fail("tests/ui/duplicate_placeholder.rs");
+    t.compile_fail("tests/ui/recursive_type.rs");
+    t.compile_fail("tests/ui/grouping_decimal_conflict.rs");
+    t.compile_fail("tests/ui/twos_complement_accounting.rs");
+    t.compile_fail("tests/ui/duplicates_vec.rs");
+    t.compile_fail("tests/ui/max_len_number.rs");
+    t.compile_fail("tests/ui/range_integer.rs");
+}
@@ -0,0 +1,10 @@
+use reformation::Reformation;
+
+#[derive(Reformation)]
+#[reformation(r"{x}:{y}:{x}")]
+struct Triple{
+    x: i32,
+    y: i32,
+}
+
+fn main(){}
@@ -0,0 +1,15 @@
+use reformation::Reformation;
+
+#[derive(Reformation, Debug, PartialEq)]
+#[reformation(r"^{x}:{y}:{x}$")]
+struct Triple{
+    #[reformation(reuse)]
+    x: i32,
+    y: i32,
+}
+
+fn main(){
+    assert_eq!(Triple::captures_count(), 3);
+    assert_eq!("1:2:1".parse::<Triple>().unwrap(), Triple{x: 1, y: 2});
+    assert!("1:2:3".parse::<Triple>().is_err());
+}
@@ -0,0 +1,10 @@
+use reformation::Reformation;
+
+#[derive(Reformation)]
+#[reformation(r"{items}")]
+struct Items{
+    #[reformation(separator = ",", duplicates = "error")]
+    items: Vec<u8>,
+}
+
+fn main(){}
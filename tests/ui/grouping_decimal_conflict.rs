@@ -0,0 +1,10 @@
+use reformation::Reformation;
+
+#[derive(Reformation)]
+#[reformation(r"{amount}")]
+struct Amount{
+    #[reformation(grouping = ",", decimal = ",")]
+    amount: f64,
+}
+
+fn main(){}
@@ -0,0 +1,10 @@
+use reformation::Reformation;
+
+#[derive(Reformation)]
+#[reformation(r"{id}")]
+struct Id{
+    #[reformation(max_len = 4)]
+    id: u32,
+}
+
+fn main(){}
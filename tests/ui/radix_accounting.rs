@@ -0,0 +1,15 @@
+use reformation::Reformation;
+
+// `radix` can be combined with `accounting`, unlike `twos_complement`:
+// parentheses are turned into minus sign before digits are parsed in radix.
+#[derive(Reformation)]
+#[reformation(r"{balance}")]
+struct Balance{
+    #[reformation(radix = 16, accounting)]
+    balance: i32,
+}
+
+fn main(){
+    let b: Balance = "(ff)".parse().unwrap();
+    assert_eq!(b.balance, -255);
+}
@@ -0,0 +1,10 @@
+use reformation::Reformation;
+
+#[derive(Reformation)]
+#[reformation(r"{x}")]
+struct Level{
+    #[reformation(range = "0.5..=10")]
+    x: u8,
+}
+
+fn main(){}
@@ -0,0 +1,10 @@
+use reformation::Reformation;
+
+#[derive(Reformation)]
+#[reformation(r"\({left}, {right}\)")]
+struct Tree{
+    left: Box<Tree>,
+    right: u32,
+}
+
+fn main(){}
@@ -0,0 +1,10 @@
+use reformation::Reformation;
+
+#[derive(Reformation)]
+#[reformation(r"{register}")]
+struct Register{
+    #[reformation(radix = 16, twos_complement, accounting)]
+    register: i8,
+}
+
+fn main(){}